@@ -1,19 +1,32 @@
+from ._polars import scan_sheet
 from ._python_calamine import (
     CalamineError,
     CalamineSheet,
     CalamineTable,
     CalamineWorkbook,
+    DefinedName,
+    DefinedNameNotFound,
+    DocumentProperties,
+    FormatInfo,
+    InvalidPasswordError,
     PasswordError,
+    Picture,
     SheetMetadata,
     SheetTypeEnum,
     SheetVisibleEnum,
     TableNotFound,
     TablesNotLoaded,
     TablesNotSupported,
+    VbaModule,
+    VbaModuleTypeEnum,
+    VbaProject,
+    VbaReference,
     WorkbookClosed,
+    WorkbookFormatEnum,
     WorksheetNotFound,
     XmlError,
     ZipError,
+    detect_format,
     load_workbook,
 )
 
@@ -22,16 +35,29 @@ __all__ = (
     "CalamineSheet",
     "CalamineTable",
     "CalamineWorkbook",
+    "DefinedName",
+    "DefinedNameNotFound",
+    "DocumentProperties",
+    "FormatInfo",
+    "InvalidPasswordError",
     "PasswordError",
+    "Picture",
     "SheetMetadata",
     "SheetTypeEnum",
     "SheetVisibleEnum",
     "TableNotFound",
     "TablesNotLoaded",
     "TablesNotSupported",
+    "VbaModule",
+    "VbaModuleTypeEnum",
+    "VbaProject",
+    "VbaReference",
     "WorkbookClosed",
+    "WorkbookFormatEnum",
     "WorksheetNotFound",
     "XmlError",
     "ZipError",
+    "detect_format",
     "load_workbook",
+    "scan_sheet",
 )
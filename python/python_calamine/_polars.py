@@ -0,0 +1,75 @@
+from __future__ import annotations
+
+import os
+import typing
+
+from ._python_calamine import CalamineSheet, load_workbook
+
+if typing.TYPE_CHECKING:
+    import polars as pl
+
+
+def _load_sheet(
+    path: str | os.PathLike, sheet: str | int, nrows: int | None = None
+) -> CalamineSheet:
+    # the header row is read in addition to `nrows` rows
+    nrows = None if nrows is None else nrows + 1
+    with load_workbook(path) as workbook:
+        if isinstance(sheet, int):
+            return workbook.get_sheet_by_index(sheet, nrows=nrows)
+        return workbook.get_sheet_by_name(sheet, nrows=nrows)
+
+
+def scan_sheet(
+    path: str | os.PathLike, sheet: str | int, infer_schema_length: int | None = 100
+) -> pl.LazyFrame:
+    """Lazily read a sheet as `polars.LazyFrame`.
+
+    Only the header and the first `infer_schema_length` rows are read to get the schema.
+    The sheet is read when the frame is collected and converted to Arrow record batches,
+    XLSX and XLSB sheets up to the first `n_rows` rows only. Only the selected columns
+    are converted. The first row is the header, see `CalamineSheet.__arrow_c_stream__`
+    for the column types.
+
+    Args:
+        path (str | os.PathLike): path to file.
+        sheet (str | int): name or index of the sheet.
+        infer_schema_length (int | None): number of rows to get the column types from,
+            all rows if `None`. Values of another type in later rows are converted to
+            strings in string columns and are nulls in other columns.
+    """
+    import polars as pl
+    from polars.io.plugins import register_io_source
+
+    probe = _load_sheet(path, sheet, infer_schema_length)
+    schema = pl.DataFrame(next(probe.iter_batches(n_rows=0))).schema
+    del probe
+
+    def source(
+        with_columns: list[str] | None,
+        predicate: pl.Expr | None,
+        n_rows: int | None,
+        batch_size: int | None,
+    ) -> typing.Iterator[pl.DataFrame]:
+        # with a predicate, `n_rows` counts the matching rows
+        limit = n_rows if predicate is None else None
+        calamine_sheet = _load_sheet(path, sheet, limit)
+        # the types are inferred from the same rows as the schema
+        batches = calamine_sheet.iter_batches(
+            columns=with_columns or list(schema),
+            n_rows=limit,
+            batch_size=batch_size,
+            infer_schema_length=infer_schema_length,
+        )
+        for batch in batches:
+            df = pl.DataFrame(batch)
+            if predicate is not None:
+                df = df.filter(predicate)
+            if n_rows is not None:
+                df = df.head(n_rows)
+                n_rows -= df.height
+            yield df
+            if n_rows == 0:
+                break
+
+    return register_io_source(source, schema=schema)
@@ -7,11 +7,86 @@ import os
 import types
 import typing
 
+if typing.TYPE_CHECKING:
+    import numpy
+    import pandas
+    import pyarrow
+
 @typing.type_check_only
 class ReadBuffer(typing.Protocol):
     def seek(self, __offset: int, __whence: int = ...) -> int: ...
     def read(self, __size: int = ...) -> bytes | None: ...
 
+ReadableBuffer: typing.TypeAlias = bytes | bytearray | memoryview
+"""Any object supporting the buffer protocol."""
+
+@typing.type_check_only
+class CalamineArrowBatch:
+    """Rows of a sheet or table, exported with the Arrow PyCapsule interface."""
+
+    def __len__(self) -> int: ...
+    def __arrow_c_stream__(self, requested_schema: object | None = None) -> object: ...
+
+@typing.type_check_only
+class CalamineInterchangeBuffer:
+    @property
+    def bufsize(self) -> int: ...
+    @property
+    def ptr(self) -> int: ...
+    def __dlpack__(self) -> typing.NoReturn: ...
+    def __dlpack_device__(self) -> tuple[int, None]: ...
+
+InterchangeDtype: typing.TypeAlias = tuple[int, int, str, str]
+
+@typing.type_check_only
+class CalamineInterchangeColumn:
+    """Column of the dataframe interchange protocol, converted from the cells on first use."""
+
+    def size(self) -> int: ...
+    @property
+    def offset(self) -> int: ...
+    @property
+    def dtype(self) -> InterchangeDtype: ...
+    @property
+    def describe_categorical(self) -> typing.NoReturn: ...
+    @property
+    def describe_null(self) -> tuple[int, int]: ...
+    @property
+    def null_count(self) -> int: ...
+    @property
+    def metadata(self) -> dict[str, typing.Any]: ...
+    def num_chunks(self) -> int: ...
+    def get_chunks(
+        self, n_chunks: int | None = None
+    ) -> list[CalamineInterchangeColumn]: ...
+    def get_buffers(
+        self,
+    ) -> dict[str, tuple[CalamineInterchangeBuffer, InterchangeDtype] | None]: ...
+
+@typing.type_check_only
+class CalamineInterchangeFrame:
+    """Dataframe of the dataframe interchange protocol."""
+
+    def __dataframe__(
+        self, nan_as_null: bool = False, allow_copy: bool = True
+    ) -> CalamineInterchangeFrame: ...
+    @property
+    def metadata(self) -> dict[str, typing.Any]: ...
+    def num_columns(self) -> int: ...
+    def num_rows(self) -> int: ...
+    def num_chunks(self) -> int: ...
+    def column_names(self) -> list[str]: ...
+    def get_column(self, i: int) -> CalamineInterchangeColumn: ...
+    def get_column_by_name(self, name: str) -> CalamineInterchangeColumn: ...
+    def get_columns(self) -> list[CalamineInterchangeColumn]: ...
+    def select_columns(self, indices: list[int]) -> CalamineInterchangeFrame: ...
+    def select_columns_by_name(self, names: list[str]) -> CalamineInterchangeFrame: ...
+    def get_chunks(
+        self, n_chunks: int | None = None
+    ) -> list[CalamineInterchangeFrame]: ...
+
+WorkbookFormat: typing.TypeAlias = typing.Literal["xlsx", "xlsm", "xlsb", "xls", "ods"]
+
 @typing.final
 class SheetTypeEnum(enum.Enum):
     WorkSheet = ...
@@ -29,6 +104,38 @@ class SheetVisibleEnum(enum.Enum):
     VeryHidden = ...
     """The sheet is hidden and cannot be displayed using the user interface. It is supported only by Excel formats."""
 
+@typing.final
+class WorkbookFormatEnum(enum.Enum):
+    Xls = ...
+    """Excel 97-2003 binary workbook."""
+    Xlsx = ...
+    """Office Open XML workbook."""
+    Xlsm = ...
+    """Office Open XML macro-enabled workbook."""
+    Xlsb = ...
+    """Excel binary workbook."""
+    Ods = ...
+    """OpenDocument spreadsheet."""
+    EncryptedOoxml = ...
+    """Password-protected XLSX, XLSM or XLSB workbook. The real format is hidden by encryption."""
+    Unknown = ...
+    """Not a supported workbook."""
+
+@typing.final
+class FormatInfo:
+    format: WorkbookFormatEnum
+    """Detected format."""
+    biff_version: int | None
+    """BIFF version (2, 3, 4, 5 or 8) for XLS, `None` for other formats or an unrecognised BOF record."""
+    encrypted: bool
+    """Whether the workbook is password-protected."""
+    encryption: str | None
+    """Encryption scheme of a password-protected workbook.
+
+    One of `agile`, `standard`, `extensible` (XLSX/XLSB), `xor`, `rc4`, `rc4-cryptoapi` (XLS), `odf` (ODS)
+    or `unknown`. `None` if the workbook isn't encrypted.
+    """
+
 @typing.final
 class SheetMetadata:
     name: str
@@ -75,7 +182,13 @@ class CalamineSheet:
         """Get bottom right cell position of a sheet data."""
 
     def to_python(
-        self, skip_empty_area: bool = True, nrows: int | None = None
+        self,
+        skip_empty_area: bool = True,
+        nrows: int | None = None,
+        usecols: (
+            str | typing.Sequence[int] | typing.Callable[[typing.Any], bool] | None
+        ) = None,
+        empty_value: str | None = "",
     ) -> list[
         list[
             int
@@ -86,6 +199,7 @@ class CalamineSheet:
             | datetime.date
             | datetime.datetime
             | datetime.timedelta
+            | None
         ]
     ]:
         """Returning data from sheet as list of lists.
@@ -94,10 +208,21 @@ class CalamineSheet:
             skip_empty_area (bool):
                 By default, calamine skips empty rows/cols before data.
                 For suppress this behaviour, set `skip_empty_area` to `False`.
+            usecols (str | Sequence[int] | Callable[[Any], bool] | None):
+                Columns to return, in sheet order: zero-based sheet column indices,
+                column letters like `"A:C,F"`, or a predicate called with the value of
+                each column in the first returned row. Other columns are never converted
+                to Python objects. Columns outside the sheet are ignored.
+            empty_value (str | None): value of empty cells, set to `None` to tell them
+                from cells with an empty string.
         """
 
     def iter_rows(
         self,
+        usecols: (
+            str | typing.Sequence[int] | typing.Callable[[typing.Any], bool] | None
+        ) = None,
+        empty_value: str | None = "",
     ) -> typing.Iterator[
         list[
             int
@@ -108,9 +233,166 @@ class CalamineSheet:
             | datetime.date
             | datetime.datetime
             | datetime.timedelta
+            | None
         ]
     ]:
-        """Returning data from sheet as iterator of lists."""
+        """Returning data from sheet as iterator of lists.
+
+        Args:
+            usecols (str | Sequence[int] | Callable[[Any], bool] | None):
+                the same as in `to_python`, a predicate is called with the first yielded row.
+            empty_value (str | None): the same as in `to_python`.
+        """
+
+    def iter_dicts(
+        self,
+        header_row: int = 0,
+        empty_value: str | None = "",
+    ) -> typing.Iterator[
+        dict[
+            str,
+            int
+            | float
+            | str
+            | bool
+            | datetime.time
+            | datetime.date
+            | datetime.datetime
+            | datetime.timedelta
+            | None,
+        ]
+    ]:
+        """Returning the rows after the header row as iterator of dicts keyed by the header.
+
+        Blank header cells are named `Unnamed: <column position>`, repeated names
+        get a `_1`, `_2`... suffix.
+
+        Args:
+            header_row (int): index of the header in the rows of `to_python`
+            empty_value (str | None): value of empty cells, see `to_python`.
+        """
+
+    def to_records(
+        self,
+        header_row: int = 0,
+        empty_value: str | None = "",
+    ) -> list[
+        dict[
+            str,
+            int
+            | float
+            | str
+            | bool
+            | datetime.time
+            | datetime.date
+            | datetime.datetime
+            | datetime.timedelta
+            | None,
+        ]
+    ]:
+        """Returning the rows after the header row as list of dicts, see `iter_dicts`.
+
+        Args:
+            header_row (int): index of the header in the rows of `to_python`
+            empty_value (str | None): value of empty cells, see `to_python`.
+        """
+
+    def __arrow_c_stream__(self, requested_schema: object | None = None) -> object:
+        """Export data from sheet with the Arrow PyCapsule interface.
+
+        The first row is the header, the other rows are the data. Column types are
+        inferred from the cells: int64, float64, large_utf8, bool, date32, timestamp,
+        duration or time64. Empty cells are nulls. Integers with floats give float64,
+        dates with datetimes give timestamp, other mixed columns are converted to
+        large_utf8.
+        `requested_schema` is ignored.
+
+        Returns:
+            `arrow_array_stream` PyCapsule with a single record batch
+        """
+
+    def to_arrow(self) -> pyarrow.Table:
+        """Returning data from sheet as `pyarrow.Table`, see `__arrow_c_stream__`."""
+
+    def __dataframe__(
+        self,
+        nan_as_null: bool = False,
+        allow_copy: bool = True,
+        header_row: int | None = 0,
+    ) -> CalamineInterchangeFrame:
+        """Export data from sheet with the dataframe interchange protocol.
+
+        Column types are inferred from the cells as in `__arrow_c_stream__`, times and
+        durations are strings. A column is converted when its dtype or buffers are
+        requested. Nulls are described by a byte mask. `nan_as_null` and `allow_copy`
+        are ignored.
+
+        Args:
+            header_row (int | None): index of the header in the rows of `to_python`,
+                the columns are named by their positions if `None`.
+        """
+
+    def iter_batches(
+        self,
+        columns: list[str] | None = None,
+        n_rows: int | None = None,
+        batch_size: int | None = None,
+        infer_schema_length: int | None = None,
+    ) -> typing.Iterator[CalamineArrowBatch]:
+        """Returning data from sheet as iterator of Arrow record batches.
+
+        The first row is the header, see `__arrow_c_stream__`. Column types are inferred
+        before the first batch, so every batch has the same schema. Cells are converted
+        when a batch is yielded, only for the selected columns. At least one batch is
+        yielded, it is empty if there are no rows.
+
+        Args:
+            columns (list[str] | None): names of the columns in the batches, all by default.
+            n_rows (int | None): maximum number of rows.
+            batch_size (int | None): maximum number of rows in a batch, 65536 by default.
+            infer_schema_length (int | None): number of rows to infer the column types
+                from, all if `None`. Values of another type in later rows are converted
+                to strings in string columns and are nulls in other columns.
+        """
+
+    def to_numpy(self, dtype: object | None = None) -> numpy.ndarray:
+        """Returning data from sheet as 2D `numpy.ndarray`.
+
+        The array type is inferred from all cells: float64, int64 or bool for numbers
+        and booleans, datetime64[D], datetime64[us] or timedelta64[us] for dates and
+        durations, object for times, strings and mixed cells. Empty cells are NaN or
+        NaT, int64 and bool arrays with empty cells are `numpy.ma.MaskedArray`.
+        Object arrays have `None` for empty cells.
+
+        Args:
+            dtype (object | None): converts the array with `ndarray.astype` if set.
+        """
+
+    def to_columns(self) -> dict[str, numpy.ndarray]:
+        """Returning data from sheet as dict of 1D `numpy.ndarray` columns.
+
+        The first row is the header, see `to_numpy` for the column types.
+        """
+
+    def to_pandas(
+        self,
+        header: int | None = 0,
+        index_col: int | str | None = None,
+        dtype: object | None = None,
+        parse_dates: bool = True,
+    ) -> pandas.DataFrame:
+        """Returning data from sheet as `pandas.DataFrame`.
+
+        Columns are typed arrays built from the cells, see `to_columns`.
+
+        Args:
+            header (int | None): index of the header in the rows of `to_python`,
+                the columns are numbered if `None`.
+            index_col (int | str | None): position or name of the index column.
+            dtype (object | None): type or dict of types for `DataFrame.astype`.
+            parse_dates (bool): dates and datetimes are `datetime64` columns if `True`,
+                Python objects otherwise.
+        """
 
     @property
     def merged_cell_ranges(
@@ -160,6 +442,7 @@ class CalamineTable:
 
     def to_python(
         self,
+        empty_value: str | None = "",
     ) -> list[
         list[
             int
@@ -170,9 +453,177 @@ class CalamineTable:
             | datetime.date
             | datetime.datetime
             | datetime.timedelta
+            | None
+        ]
+    ]:
+        """Returning data from table as list of lists.
+
+        Args:
+            empty_value (str | None): value of empty cells, see `CalamineSheet.to_python`.
+        """
+
+    def iter_dicts(
+        self,
+        empty_value: str | None = "",
+    ) -> typing.Iterator[
+        dict[
+            str,
+            int
+            | float
+            | str
+            | bool
+            | datetime.time
+            | datetime.date
+            | datetime.datetime
+            | datetime.timedelta
+            | None,
+        ]
+    ]:
+        """Returning data from table as iterator of dicts keyed by `columns`.
+
+        Args:
+            empty_value (str | None): value of empty cells, see `CalamineSheet.to_python`.
+        """
+
+    def to_records(
+        self,
+        empty_value: str | None = "",
+    ) -> list[
+        dict[
+            str,
+            int
+            | float
+            | str
+            | bool
+            | datetime.time
+            | datetime.date
+            | datetime.datetime
+            | datetime.timedelta
+            | None,
         ]
     ]:
-        """Returning data from table as list of lists."""
+        """Returning data from table as list of dicts keyed by `columns`.
+
+        Args:
+            empty_value (str | None): value of empty cells, see `CalamineSheet.to_python`.
+        """
+
+    def __arrow_c_stream__(self, requested_schema: object | None = None) -> object:
+        """Export data from table with the Arrow PyCapsule interface.
+
+        Columns are named by `columns`, types are inferred as in
+        `CalamineSheet.__arrow_c_stream__`.
+        """
+
+    def to_arrow(self) -> pyarrow.Table:
+        """Returning data from table as `pyarrow.Table`."""
+
+    def __dataframe__(
+        self, nan_as_null: bool = False, allow_copy: bool = True
+    ) -> CalamineInterchangeFrame:
+        """Export data from table with the dataframe interchange protocol,
+        see `CalamineSheet.__dataframe__`."""
+
+    def iter_batches(
+        self,
+        columns: list[str] | None = None,
+        n_rows: int | None = None,
+        batch_size: int | None = None,
+        infer_schema_length: int | None = None,
+    ) -> typing.Iterator[CalamineArrowBatch]:
+        """Returning data from table as iterator of Arrow record batches,
+        see `CalamineSheet.iter_batches`."""
+
+@typing.final
+class DefinedName:
+    name: str
+    """Name as defined in the workbook."""
+    scope: str | None
+    """Sheet name of a sheet-level name, `None` for a workbook-level name."""
+    formula: str
+    """Formula or reference text of the name, e.g. `Sheet1!$A$1:$D$20`."""
+
+@typing.final
+class Picture:
+    extension: str
+    """File extension of the image, e.g. `png` or `jpeg`."""
+    data: bytes
+    """Raw image data."""
+    sheet: str | None
+    """Name of the sheet the picture is placed on. `None` if unknown, positions are only read for XLSX."""
+    anchor: tuple[int, int] | None
+    """Zero-based (row, column) of the cell the top left corner of the picture is anchored to.
+    `None` if unknown."""
+    name: str | None
+    """Name of the picture object, e.g. `Picture 1`. `None` if unknown."""
+
+@typing.final
+class DocumentProperties:
+    title: str | None
+    """Title of the document."""
+    subject: str | None
+    """Subject of the document."""
+    creator: str | None
+    """Author who created the document."""
+    keywords: str | None
+    """Keywords of the document, ODS keywords are joined with a comma."""
+    last_modified_by: str | None
+    """Author of the last modification."""
+    created: datetime.datetime | None
+    """Creation time, in UTC if the document stores a timezone."""
+    modified: datetime.datetime | None
+    """Last modification time, in UTC if the document stores a timezone."""
+    company: str | None
+    """Company of the author. Not stored in ODS."""
+    application: str | None
+    """Name of the application which saved the document."""
+    app_version: str | None
+    """Version of the application which saved the document, e.g. `16.0300`."""
+
+@typing.final
+class VbaModuleTypeEnum(enum.Enum):
+    Standard = ...
+    Class = ...
+    Document = ...
+    Designer = ...
+
+@typing.final
+class VbaModule:
+    name: str
+    """Name of the module."""
+    typ: VbaModuleTypeEnum | None
+    """Type of the module. `None` if the project stream can't be read."""
+    source: str
+    """Decoded source code of the module, including `Attribute` lines."""
+
+@typing.final
+class VbaReference:
+    name: str
+    """Name of the referenced library or project."""
+    description: str
+    """Description of the reference."""
+    path: str
+    """Location of the reference, as stored in the project."""
+
+@typing.final
+class VbaProject:
+    modules: list[VbaModule]
+    """All modules of the project, sorted by name."""
+    references: list[VbaReference]
+    """All references of the project."""
+    @property
+    def module_names(self) -> list[str]:
+        """Names of all modules of the project, sorted."""
+
+    def get_module(self, name: str) -> VbaModule | None:
+        """Get module by name.
+
+        Args:
+            name(str): name of module
+
+        Returns:
+            VbaModule or None if module not found
+        """
 
 @typing.final
 class CalamineWorkbook:
@@ -184,37 +635,127 @@ class CalamineWorkbook:
     """All sheets metadata of this workbook, in workbook order."""
     table_names: list[str] | None
     """All table names of this workbook."""
+    format: WorkbookFormatEnum
+    """Format of this workbook, one of `Xls`, `Xlsx`, `Xlsb` or `Ods`.
+
+    XLSM workbooks are read as `Xlsx`, see `macros_enabled`.
+    """
+    macros_enabled: bool
+    """Whether this workbook is macro-enabled (XLSM) or contains a VBA project."""
+    biff_version: int | None
+    """BIFF version (2, 3, 4, 5 or 8) for XLS, `None` for other formats or an unrecognised BOF record."""
+    date_system: typing.Literal[1900, 1904] | None
+    """Date system used by serial dates of this workbook. `None` for ODS, which stores dates as is."""
+    vba_project: VbaProject | None
+    """VBA project of this workbook. `None` if the workbook has no macros or is ODS.
+
+    Raises:
+        WorkbookClosed: If workbook already closed.
+    """
+    document_properties: DocumentProperties
+    """Core and application properties of this workbook."""
+    custom_properties: dict[str, str | int | float | bool | datetime.date | datetime.datetime]
+    """Custom properties of this workbook, in document order.
+
+    Values keep their type, numbers stored as floats by ODS are returned as floats.
+    """
+    pictures: list[Picture]
+    """All embedded pictures of this workbook.
+
+    Raises:
+        WorkbookClosed: If workbook already closed.
+    """
+    defined_names: list[DefinedName]
+    """All defined names of this workbook, in workbook order.
+
+    ODS sheet-level names are not read.
+    """
     @classmethod
     def from_object(
-        cls, path_or_filelike: str | os.PathLike | ReadBuffer, load_tables: bool = False
+        cls,
+        path_or_filelike: str | os.PathLike | ReadableBuffer | ReadBuffer,
+        load_tables: bool = False,
+        mmap: bool = False,
+        format: WorkbookFormat | None = None,
+        password: str | None = None,
     ) -> "CalamineWorkbook":
         """Determining type of pyobject and reading from it.
 
         Args:
-            path_or_filelike (str | os.PathLike | ReadBuffer): path to file or IO (must implement read/seek methods).
+            path_or_filelike (str | os.PathLike | ReadableBuffer | ReadBuffer): path to file, bytes-like object or IO (must implement read/seek methods).
             load_tables (bool): load Excel tables (supported for XLSX only).
+            mmap (bool): memory-map the file instead of reading it.
+                Supported for paths only, `ValueError` is raised for other objects.
+            format (WorkbookFormat | None): open the file with the reader of this format.
+                By default the format is detected by file extension or content.
+            password (str | None): password of an encrypted workbook (RC4 XLS, Standard or Agile encrypted XLSX/XLSB, encrypted ODS).
+                It is ignored for unencrypted workbooks. Encrypted workbooks are decrypted in memory.
         """
 
     @classmethod
     def from_path(
-        cls, path: str | os.PathLike, load_tables: bool = False
+        cls,
+        path: str | os.PathLike,
+        load_tables: bool = False,
+        mmap: bool = False,
+        format: WorkbookFormat | None = None,
+        password: str | None = None,
     ) -> "CalamineWorkbook":
         """Reading file from path.
 
         Args:
             path (str | os.PathLike): path to file.
             load_tables (bool): load Excel tables (supported for XLSX only).
+            mmap (bool): memory-map the file instead of reading it.
+                The file must not be modified while the workbook is open.
+            format (WorkbookFormat | None): open the file with the reader of this format.
+                By default the format is detected by file extension or content.
+            password (str | None): password of an encrypted workbook (RC4 XLS, Standard or Agile encrypted XLSX/XLSB, encrypted ODS).
+                It is ignored for unencrypted workbooks. Encrypted workbooks are decrypted in memory.
         """
 
     @classmethod
     def from_filelike(
-        cls, filelike: ReadBuffer, load_tables: bool = False
+        cls,
+        filelike: ReadBuffer,
+        load_tables: bool = False,
+        format: WorkbookFormat | None = None,
+        password: str | None = None,
     ) -> "CalamineWorkbook":
         """Reading file from IO.
 
+        Seekable IO is read lazily and must stay open until the workbook is closed,
+        other IO is read into memory.
+
         Args:
             filelike : IO (must implement read/seek methods).
             load_tables (bool): load Excel tables (supported for XLSX only).
+            format (WorkbookFormat | None): open the file with the reader of this format.
+                By default the format is detected by content.
+            password (str | None): password of an encrypted workbook (RC4 XLS, Standard or Agile encrypted XLSX/XLSB, encrypted ODS).
+                It is ignored for unencrypted workbooks. Encrypted workbooks are decrypted in memory.
+        """
+
+    @classmethod
+    def from_buffer(
+        cls,
+        buffer: ReadableBuffer,
+        load_tables: bool = False,
+        format: WorkbookFormat | None = None,
+        password: str | None = None,
+    ) -> "CalamineWorkbook":
+        """Reading file from bytes-like object without copying.
+
+        Accepts any object supporting the buffer protocol (bytes, bytearray, memoryview, mmap, ...).
+        The object is kept alive while the workbook is open and must not be modified.
+
+        Args:
+            buffer (ReadableBuffer): bytes-like object.
+            load_tables (bool): load Excel tables (supported for XLSX only).
+            format (WorkbookFormat | None): open the file with the reader of this format.
+                By default the format is detected by content.
+            password (str | None): password of an encrypted workbook (RC4 XLS, Standard or Agile encrypted XLSX/XLSB, encrypted ODS).
+                It is ignored for unencrypted workbooks. Encrypted workbooks are decrypted in memory.
         """
 
     def close(self) -> None:
@@ -234,11 +775,30 @@ class CalamineWorkbook:
         exc_val: BaseException | None,
         exc_tb: types.TracebackType | None,
     ) -> None: ...
-    def get_sheet_by_name(self, name: str) -> CalamineSheet:
+    def get_sheet_by_name(
+        self,
+        name: str,
+        nrows: int | None = None,
+        skiprows: int = 0,
+        header_row: int | None = None,
+    ) -> CalamineSheet:
         """Get worksheet by name.
 
+        With `header_row` the sheet starts at this row even if it's empty,
+        rows above it are dropped. By default it starts at the first non-empty row.
+
+        With `nrows` or `skiprows` only the rows `skiprows` to `skiprows + nrows`
+        of the worksheet, counted from the header row or the first non-empty row
+        as `CalamineSheet.to_python` does, are read. Cells keep their positions in
+        the worksheet, merged cells are clipped to the rows read. XLSX and XLSB worksheets
+        are parsed up to the last row only, the columns are the ones of the dimension
+        stored in the worksheet. XLS and ODS worksheets are read as a whole first.
+
         Args:
             name(str): name of worksheet
+            nrows(int | None): number of rows to read, all if `None`
+            skiprows(int): number of rows to skip
+            header_row(int | None): zero-based index of the first row
 
         Returns:
             CalamineSheet
@@ -248,11 +808,20 @@ class CalamineWorkbook:
             WorksheetNotFound: If worksheet not found in workbook.
         """
 
-    def get_sheet_by_index(self, index: int) -> CalamineSheet:
+    def get_sheet_by_index(
+        self,
+        index: int,
+        nrows: int | None = None,
+        skiprows: int = 0,
+        header_row: int | None = None,
+    ) -> CalamineSheet:
         """Get worksheet by index.
 
         Args:
             index(int): index of worksheet
+            nrows(int | None): the same as in `get_sheet_by_name`
+            skiprows(int): the same as in `get_sheet_by_name`
+            header_row(int | None): the same as in `get_sheet_by_name`
 
         Returns:
             CalamineSheet
@@ -262,6 +831,94 @@ class CalamineWorkbook:
             WorksheetNotFound: If worksheet not found in workbook.
         """
 
+    def iter_sheet_rows(
+        self,
+        name: str,
+        usecols: (
+            str | typing.Sequence[int] | typing.Callable[[typing.Any], bool] | None
+        ) = None,
+        empty_value: str | None = "",
+    ) -> typing.Iterator[
+        list[
+            int
+            | float
+            | str
+            | bool
+            | datetime.time
+            | datetime.date
+            | datetime.datetime
+            | datetime.timedelta
+            | None
+        ]
+    ]:
+        """Iterate over the rows of worksheet by name, starting from `A1`.
+
+        XLSX and XLSB worksheets are parsed as the rows are consumed, so the whole sheet
+        is never held in memory. Rows are padded to the dimension stored in the sheet,
+        or, without one, to the widest row read so far, so earlier rows may be shorter.
+        XLS and ODS worksheets are read as a whole first.
+        Rows are the same as `CalamineSheet.to_python(skip_empty_area=False)`
+        when the stored dimension is accurate.
+
+        Args:
+            name(str): name of worksheet
+            usecols(str | Sequence[int] | Callable[[Any], bool] | None):
+                the same as in `CalamineSheet.to_python`. Cells of other columns
+                are skipped while the worksheet is parsed. A predicate is called with
+                the first row with a value, padded like the yielded rows.
+            empty_value(str | None): the same as in `CalamineSheet.to_python`,
+                also used for the padding of the rows.
+
+        Returns:
+            Iterator over rows
+
+        Raises:
+            WorkbookClosed: If workbook already closed.
+            WorksheetNotFound: If worksheet not found in workbook.
+        """
+
+    def get_sheet_formulas(
+        self, name: str, skip_empty_area: bool = True
+    ) -> list[list[str]]:
+        """Get formulas of worksheet by name.
+
+        The grid has the same shape as `CalamineSheet.to_python` of the worksheet,
+        so `[row][col]` gives both the formula and its cached value. Formulas without
+        a cached value outside of the cells with values are left out.
+        Formulas are returned as stored in the file, e.g. `SUM(A1:A3)` for Excel formats
+        and `of:=SUM([.A1:.A3])` for ODS.
+
+        Args:
+            name(str): name of worksheet
+            skip_empty_area(bool): the same as in `CalamineSheet.to_python`
+
+        Returns:
+            list[list[str]]: formulas, an empty string for cells without formula
+
+        Raises:
+            WorkbookClosed: If workbook already closed.
+            WorksheetNotFound: If worksheet not found in workbook.
+        """
+
+    def get_range_by_name(self, name: str) -> CalamineSheet:
+        """Get the cells of a defined name which refers to a single cell or area,
+        e.g. `Sheet1!$A$1:$D$20`, or to whole columns or rows, e.g. `Sheet1!$A:$C`.
+        The area is clipped to the cells of the worksheet with values.
+
+        A workbook-level name takes precedence over sheet-level names with the same name.
+
+        Args:
+            name(str): name as defined in the workbook
+
+        Returns:
+            CalamineSheet: part of the referenced worksheet, `start` and `end` are positions in the worksheet
+
+        Raises:
+            WorkbookClosed: If workbook already closed.
+            DefinedNameNotFound: If the name is not defined in workbook.
+            CalamineError: If the name doesn't refer to a cell or area.
+        """
+
     def get_table_by_name(self, name: str) -> CalamineTable:
         """Get table by name.
 
@@ -278,6 +935,7 @@ class CalamineWorkbook:
 
 class CalamineError(Exception): ...
 class PasswordError(CalamineError): ...
+class InvalidPasswordError(PasswordError): ...
 class WorksheetNotFound(CalamineError): ...
 class XmlError(CalamineError): ...
 class ZipError(CalamineError): ...
@@ -285,15 +943,48 @@ class WorkbookClosed(CalamineError): ...
 class TablesNotLoaded(CalamineError): ...
 class TablesNotSupported(CalamineError): ...
 class TableNotFound(CalamineError): ...
+class DefinedNameNotFound(CalamineError): ...
 
 def load_workbook(
-    path_or_filelike: str | os.PathLike | ReadBuffer, load_tables: bool = False
+    path_or_filelike: str | os.PathLike | ReadableBuffer | ReadBuffer,
+    load_tables: bool = False,
+    mmap: bool = False,
+    format: WorkbookFormat | None = None,
+    password: str | None = None,
 ) -> CalamineWorkbook:
     """Determining type of pyobject and reading from it.
 
     Args:
-        path_or_filelike (str | os.PathLike | ReadBuffer): path to file or IO (must implement read/seek methods).
+        path_or_filelike (str | os.PathLike | ReadableBuffer | ReadBuffer): path to file, bytes-like object or IO (must implement read/seek methods).
         load_tables (bool): load Excel tables (supported for XLSX only).
+        mmap (bool): memory-map the file instead of reading it.
+            Supported for paths only, `ValueError` is raised for other objects.
+            The file must not be modified while the workbook is open.
+        format (WorkbookFormat | None): open the file with the reader of this format.
+            By default the format is detected by file extension or content.
+        password (str | None): password of an encrypted workbook (RC4 XLS, Standard or Agile encrypted XLSX/XLSB, encrypted ODS).
+            It is ignored for unencrypted workbooks. Encrypted workbooks are decrypted in memory.
+
+    Raises:
+        CalamineError: If the format isn't given and no reader could open the file.
+        PasswordError: If the workbook is encrypted and the password isn't given or the encryption isn't supported.
+        InvalidPasswordError: If the password is wrong.
+    """
+
+def detect_format(
+    path_or_filelike: str | os.PathLike | ReadableBuffer | ReadBuffer,
+) -> FormatInfo:
+    """Detect format of a workbook without opening it.
+
+    Only the signature and the container structure (compound file directory or zip entries) are read,
+    so this is cheap even for large files. Unlike `load_workbook`, the file extension is ignored.
+
+    Args:
+        path_or_filelike (str | os.PathLike | ReadableBuffer | ReadBuffer): path to file, bytes-like object or IO (must implement read/seek/tell methods).
+            The position of IO is restored afterwards, also when an error is raised.
+
+    Returns:
+        FormatInfo
     """
 
 __all__ = [
@@ -301,16 +992,28 @@ __all__ = [
     "CalamineSheet",
     "CalamineTable",
     "CalamineWorkbook",
+    "DefinedName",
+    "DefinedNameNotFound",
+    "DocumentProperties",
+    "FormatInfo",
+    "InvalidPasswordError",
     "PasswordError",
+    "Picture",
     "SheetMetadata",
     "SheetTypeEnum",
     "SheetVisibleEnum",
     "TableNotFound",
     "TablesNotLoaded",
     "TablesNotSupported",
+    "VbaModule",
+    "VbaModuleTypeEnum",
+    "VbaProject",
+    "VbaReference",
     "WorkbookClosed",
+    "WorkbookFormatEnum",
     "WorksheetNotFound",
     "XmlError",
     "ZipError",
+    "detect_format",
     "load_workbook",
 ]
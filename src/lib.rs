@@ -2,19 +2,29 @@ use pyo3::prelude::*;
 
 mod types;
 use crate::types::{
-    CalamineError, CalamineSheet, CalamineTable, CalamineWorkbook, CellValue, Error, PasswordError,
-    SheetMetadata, SheetTypeEnum, SheetVisibleEnum, TableNotFound, TablesNotLoaded,
-    TablesNotSupported, WorkbookClosed, WorksheetNotFound, XmlError, ZipError,
+    CalamineError, CalamineSheet, CalamineTable, CalamineWorkbook, CellValue, DefinedName,
+    DefinedNameNotFound, DocumentProperties, Error, FormatInfo, InvalidPasswordError,
+    PasswordError, Picture, SheetMetadata, SheetTypeEnum, SheetVisibleEnum, TableNotFound,
+    TablesNotLoaded, TablesNotSupported, VbaModule, VbaModuleTypeEnum, VbaProject, VbaReference,
+    WorkbookClosed, WorkbookFormatEnum, WorksheetNotFound, XmlError, ZipError,
 };
 
 #[pyfunction]
-#[pyo3(signature = (path_or_filelike, load_tables=false))]
+#[pyo3(signature = (path_or_filelike, load_tables=false, mmap=false, format=None, password=None))]
 fn load_workbook(
     py: Python,
     path_or_filelike: Py<PyAny>,
     load_tables: bool,
+    mmap: bool,
+    format: Option<&str>,
+    password: Option<&str>,
 ) -> PyResult<CalamineWorkbook> {
-    CalamineWorkbook::from_object(py, path_or_filelike, load_tables)
+    CalamineWorkbook::from_object(py, path_or_filelike, load_tables, mmap, format, password)
+}
+
+#[pyfunction]
+fn detect_format(py: Python, path_or_filelike: Py<PyAny>) -> PyResult<FormatInfo> {
+    FormatInfo::from_object(py, path_or_filelike)
 }
 
 #[pymodule]
@@ -25,9 +35,23 @@ fn _python_calamine(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<SheetMetadata>()?;
     m.add_class::<SheetTypeEnum>()?;
     m.add_class::<SheetVisibleEnum>()?;
+    m.add_function(wrap_pyfunction!(detect_format, m)?)?;
     m.add_class::<CalamineTable>()?;
+    m.add_class::<DefinedName>()?;
+    m.add_class::<Picture>()?;
+    m.add_class::<DocumentProperties>()?;
+    m.add_class::<VbaProject>()?;
+    m.add_class::<VbaModule>()?;
+    m.add_class::<VbaModuleTypeEnum>()?;
+    m.add_class::<VbaReference>()?;
+    m.add_class::<FormatInfo>()?;
+    m.add_class::<WorkbookFormatEnum>()?;
     m.add("CalamineError", py.get_type::<CalamineError>())?;
     m.add("PasswordError", py.get_type::<PasswordError>())?;
+    m.add(
+        "InvalidPasswordError",
+        py.get_type::<InvalidPasswordError>(),
+    )?;
     m.add("WorksheetNotFound", py.get_type::<WorksheetNotFound>())?;
     m.add("XmlError", py.get_type::<XmlError>())?;
     m.add("ZipError", py.get_type::<ZipError>())?;
@@ -35,5 +59,6 @@ fn _python_calamine(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add("TablesNotLoaded", py.get_type::<TablesNotLoaded>())?;
     m.add("TableNotFound", py.get_type::<TableNotFound>())?;
     m.add("WorkbookClosed", py.get_type::<WorkbookClosed>())?;
+    m.add("DefinedNameNotFound", py.get_type::<DefinedNameNotFound>())?;
     Ok(())
 }
@@ -0,0 +1,389 @@
+//! Export of sheet columns with the Arrow C stream interface,
+//! see https://arrow.apache.org/docs/format/CStreamInterface.html
+
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::ptr::{null, null_mut};
+
+use chrono::{NaiveDate, NaiveTime};
+use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
+
+use crate::types::columns::{to_string, Column};
+
+const ARROW_FLAG_NULLABLE: i64 = 2;
+
+#[repr(C)]
+struct ArrowSchema {
+    format: *const c_char,
+    name: *const c_char,
+    metadata: *const c_char,
+    flags: i64,
+    n_children: i64,
+    children: *mut *mut ArrowSchema,
+    dictionary: *mut ArrowSchema,
+    release: Option<unsafe extern "C" fn(*mut ArrowSchema)>,
+    private_data: *mut c_void,
+}
+
+#[repr(C)]
+struct ArrowArray {
+    length: i64,
+    null_count: i64,
+    offset: i64,
+    n_buffers: i64,
+    n_children: i64,
+    buffers: *mut *const c_void,
+    children: *mut *mut ArrowArray,
+    dictionary: *mut ArrowArray,
+    release: Option<unsafe extern "C" fn(*mut ArrowArray)>,
+    private_data: *mut c_void,
+}
+
+#[repr(C)]
+struct ArrowArrayStream {
+    get_schema: Option<unsafe extern "C" fn(*mut ArrowArrayStream, *mut ArrowSchema) -> c_int>,
+    get_next: Option<unsafe extern "C" fn(*mut ArrowArrayStream, *mut ArrowArray) -> c_int>,
+    get_last_error: Option<unsafe extern "C" fn(*mut ArrowArrayStream) -> *const c_char>,
+    release: Option<unsafe extern "C" fn(*mut ArrowArrayStream)>,
+    private_data: *mut c_void,
+}
+
+// SAFETY: the stream owns its data and is used by one consumer at a time
+unsafe impl Send for ArrowArrayStream {}
+
+enum Buffer {
+    U8(Vec<u8>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    F64(Vec<f64>),
+}
+
+impl Buffer {
+    fn as_ptr(&self) -> *const c_void {
+        match self {
+            Buffer::U8(v) => v.as_ptr().cast(),
+            Buffer::I32(v) => v.as_ptr().cast(),
+            Buffer::I64(v) => v.as_ptr().cast(),
+            Buffer::F64(v) => v.as_ptr().cast(),
+        }
+    }
+}
+
+/// An array with the buffers of its type, a missing validity buffer means no nulls.
+struct ArrayData {
+    length: usize,
+    null_count: usize,
+    buffers: Vec<Option<Buffer>>,
+    children: Vec<ArrayData>,
+}
+
+/// A field of the record batch with its format string.
+struct Field {
+    name: CString,
+    format: &'static CStr,
+}
+
+fn bitmap(bits: &[bool]) -> Vec<u8> {
+    let mut bitmap = vec![0u8; bits.len().div_ceil(8)];
+    for (i, _) in bits.iter().enumerate().filter(|(_, bit)| **bit) {
+        bitmap[i / 8] |= 1 << (i % 8);
+    }
+    bitmap
+}
+
+fn primitive<T: Default>(
+    values: impl Iterator<Item = Option<T>>,
+    buffer: fn(Vec<T>) -> Buffer,
+) -> ArrayData {
+    let mut valid = Vec::new();
+    let mut data = Vec::new();
+    for value in values {
+        valid.push(value.is_some());
+        data.push(value.unwrap_or_default());
+    }
+    with_validity(valid, vec![Some(buffer(data))])
+}
+
+fn with_validity(valid: Vec<bool>, buffers: Vec<Option<Buffer>>) -> ArrayData {
+    let null_count = valid.iter().filter(|valid| !**valid).count();
+    let validity = (null_count > 0).then(|| Buffer::U8(bitmap(&valid)));
+    ArrayData {
+        length: valid.len(),
+        null_count,
+        buffers: std::iter::once(validity).chain(buffers).collect(),
+        children: Vec::new(),
+    }
+}
+
+fn column_array(column: Column) -> (&'static CStr, ArrayData) {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    match column {
+        Column::Null(length) => (
+            c"n",
+            ArrayData {
+                length,
+                null_count: length,
+                buffers: Vec::new(),
+                children: Vec::new(),
+            },
+        ),
+        Column::Int(values) => (c"l", primitive(values.into_iter(), Buffer::I64)),
+        Column::Float(values) => (c"g", primitive(values.into_iter(), Buffer::F64)),
+        Column::Bool(values) => {
+            let valid = values.iter().map(Option::is_some).collect();
+            let bits: Vec<_> = values.iter().map(|v| v.unwrap_or_default()).collect();
+            (
+                c"b",
+                with_validity(valid, vec![Some(Buffer::U8(bitmap(&bits)))]),
+            )
+        }
+        Column::Mixed(values) => {
+            column_array(Column::String(values.into_iter().map(to_string).collect()))
+        }
+        Column::String(values) => {
+            // large_utf8, the text of a column may not fit i32 offsets
+            let mut offsets = vec![0i64];
+            let mut data = Vec::new();
+            for value in &values {
+                data.extend_from_slice(value.as_deref().unwrap_or_default().as_bytes());
+                offsets.push(data.len() as i64);
+            }
+            let valid = values.iter().map(Option::is_some).collect();
+            let buffers = vec![Some(Buffer::I64(offsets)), Some(Buffer::U8(data))];
+            (c"U", with_validity(valid, buffers))
+        }
+        Column::Date(values) => (
+            c"tdD",
+            primitive(
+                values
+                    .into_iter()
+                    .map(|v| v.map(|v| v.signed_duration_since(epoch).num_days() as i32)),
+                Buffer::I32,
+            ),
+        ),
+        Column::DateTime(values) => (
+            c"tsu:",
+            primitive(
+                values
+                    .into_iter()
+                    .map(|v| v.map(|v| v.and_utc().timestamp_micros())),
+                Buffer::I64,
+            ),
+        ),
+        Column::Time(values) => (
+            c"ttu",
+            primitive(
+                values
+                    .into_iter()
+                    .map(|v| v.and_then(|v| (v - NaiveTime::MIN).num_microseconds())),
+                Buffer::I64,
+            ),
+        ),
+        Column::Timedelta(values) => (
+            c"tDu",
+            primitive(
+                values
+                    .into_iter()
+                    .map(|v| v.and_then(|v| v.num_microseconds())),
+                Buffer::I64,
+            ),
+        ),
+    }
+}
+
+struct ArrayPrivate {
+    _buffers: Vec<Option<Buffer>>,
+    buffer_ptrs: Vec<*const c_void>,
+    children: Vec<*mut ArrowArray>,
+}
+
+unsafe extern "C" fn release_array(array: *mut ArrowArray) {
+    let Some(array) = (unsafe { array.as_mut() }) else {
+        return;
+    };
+    let private = unsafe { Box::from_raw(array.private_data.cast::<ArrayPrivate>()) };
+    for child in private.children {
+        // a child moved by the consumer is already released
+        if let Some(release) = unsafe { (*child).release } {
+            unsafe { release(child) };
+        }
+        drop(unsafe { Box::from_raw(child) });
+    }
+    array.release = None;
+}
+
+fn export_array(data: ArrayData) -> ArrowArray {
+    let children: Vec<_> = data
+        .children
+        .into_iter()
+        .map(|child| Box::into_raw(Box::new(export_array(child))))
+        .collect();
+    let buffer_ptrs = data
+        .buffers
+        .iter()
+        .map(|buffer| buffer.as_ref().map_or(null(), Buffer::as_ptr))
+        .collect();
+    let mut private = Box::new(ArrayPrivate {
+        _buffers: data.buffers,
+        buffer_ptrs,
+        children,
+    });
+    ArrowArray {
+        length: data.length as i64,
+        null_count: data.null_count as i64,
+        offset: 0,
+        n_buffers: private.buffer_ptrs.len() as i64,
+        n_children: private.children.len() as i64,
+        buffers: private.buffer_ptrs.as_mut_ptr(),
+        children: private.children.as_mut_ptr(),
+        dictionary: null_mut(),
+        release: Some(release_array),
+        private_data: Box::into_raw(private).cast(),
+    }
+}
+
+struct SchemaPrivate {
+    _name: CString,
+    children: Vec<*mut ArrowSchema>,
+}
+
+unsafe extern "C" fn release_schema(schema: *mut ArrowSchema) {
+    let Some(schema) = (unsafe { schema.as_mut() }) else {
+        return;
+    };
+    let private = unsafe { Box::from_raw(schema.private_data.cast::<SchemaPrivate>()) };
+    for child in private.children {
+        if let Some(release) = unsafe { (*child).release } {
+            unsafe { release(child) };
+        }
+        drop(unsafe { Box::from_raw(child) });
+    }
+    schema.release = None;
+}
+
+fn export_schema(
+    format: &'static CStr,
+    name: CString,
+    flags: i64,
+    children: Vec<ArrowSchema>,
+) -> ArrowSchema {
+    let children = children
+        .into_iter()
+        .map(|child| Box::into_raw(Box::new(child)))
+        .collect();
+    let mut private = Box::new(SchemaPrivate {
+        _name: name,
+        children,
+    });
+    ArrowSchema {
+        format: format.as_ptr(),
+        name: private._name.as_ptr(),
+        metadata: null(),
+        flags,
+        n_children: private.children.len() as i64,
+        children: private.children.as_mut_ptr(),
+        dictionary: null_mut(),
+        release: Some(release_schema),
+        private_data: Box::into_raw(private).cast(),
+    }
+}
+
+/// The fields and the only record batch of a stream.
+struct StreamPrivate {
+    fields: Vec<Field>,
+    batch: Option<ArrayData>,
+}
+
+unsafe extern "C" fn get_schema(stream: *mut ArrowArrayStream, out: *mut ArrowSchema) -> c_int {
+    let private = unsafe { &*(*stream).private_data.cast::<StreamPrivate>() };
+    let fields = private
+        .fields
+        .iter()
+        .map(|field| {
+            export_schema(
+                field.format,
+                field.name.clone(),
+                ARROW_FLAG_NULLABLE,
+                vec![],
+            )
+        })
+        .collect();
+    unsafe { out.write(export_schema(c"+s", CString::default(), 0, fields)) };
+    0
+}
+
+unsafe extern "C" fn get_next(stream: *mut ArrowArrayStream, out: *mut ArrowArray) -> c_int {
+    let private = unsafe { &mut *(*stream).private_data.cast::<StreamPrivate>() };
+    let array = match private.batch.take() {
+        Some(batch) => export_array(batch),
+        // a released array marks the end of the stream
+        None => ArrowArray {
+            length: 0,
+            null_count: 0,
+            offset: 0,
+            n_buffers: 0,
+            n_children: 0,
+            buffers: null_mut(),
+            children: null_mut(),
+            dictionary: null_mut(),
+            release: None,
+            private_data: null_mut(),
+        },
+    };
+    unsafe { out.write(array) };
+    0
+}
+
+unsafe extern "C" fn get_last_error(_stream: *mut ArrowArrayStream) -> *const c_char {
+    null()
+}
+
+unsafe extern "C" fn release_stream(stream: *mut ArrowArrayStream) {
+    let Some(stream) = (unsafe { stream.as_mut() }) else {
+        return;
+    };
+    drop(unsafe { Box::from_raw(stream.private_data.cast::<StreamPrivate>()) });
+    stream.release = None;
+}
+
+/// Exports the columns as a stream of one record batch in an `arrow_array_stream` capsule,
+/// for `__arrow_c_stream__` of the Arrow PyCapsule interface.
+pub fn arrow_c_stream(
+    py: Python<'_>,
+    names: Vec<String>,
+    columns: Vec<Column>,
+) -> PyResult<Bound<'_, PyCapsule>> {
+    let length = columns.first().map_or(0, Column::len);
+    let (fields, children) = names
+        .into_iter()
+        .zip(columns)
+        .map(|(name, column)| {
+            let (format, data) = column_array(column);
+            let name = CString::new(name.replace('\0', "")).unwrap_or_default();
+            (Field { name, format }, data)
+        })
+        .unzip();
+    let batch = ArrayData {
+        length,
+        null_count: 0,
+        buffers: vec![None],
+        children,
+    };
+    let private = Box::new(StreamPrivate {
+        fields,
+        batch: Some(batch),
+    });
+    let stream = ArrowArrayStream {
+        get_schema: Some(get_schema),
+        get_next: Some(get_next),
+        get_last_error: Some(get_last_error),
+        release: Some(release_stream),
+        private_data: Box::into_raw(private).cast(),
+    };
+    PyCapsule::new_with_value_and_destructor(py, stream, c"arrow_array_stream", |mut stream, _| {
+        // not released if the stream wasn't moved out by a consumer
+        if let Some(release) = stream.release {
+            unsafe { release(&mut stream) };
+        }
+    })
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use calamine::{Data, Range};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
+
+use crate::types::arrow::arrow_c_stream;
+use crate::types::columns::{column_kinds, Column, Kind};
+use crate::CellValue;
+
+const DEFAULT_BATCH_SIZE: usize = 65_536;
+
+/// Rows of some columns, exported with the Arrow PyCapsule interface.
+#[pyclass]
+pub struct CalamineArrowBatch {
+    names: Vec<String>,
+    columns: Vec<Column>,
+}
+
+#[pymethods]
+impl CalamineArrowBatch {
+    fn __len__(&self) -> usize {
+        self.columns.first().map_or(0, Column::len)
+    }
+
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        let _ = requested_schema;
+        arrow_c_stream(py, self.names.clone(), self.columns.clone())
+    }
+}
+
+/// Options of `iter_batches`, the same for sheets and tables.
+pub struct BatchOptions {
+    pub columns: Option<Vec<String>>,
+    pub n_rows: Option<usize>,
+    pub batch_size: Option<usize>,
+    pub infer_schema_length: Option<usize>,
+}
+
+/// Iterates over batches of the rows after `skip`, only the selected columns are converted.
+/// The column types are inferred from the first `infer_schema_length` rows or all rows
+/// before the first batch, so every batch has the same schema.
+#[pyclass]
+pub struct CalamineBatchIterator {
+    range: Arc<Range<Data>>,
+    names: Vec<String>,
+    positions: Vec<usize>,
+    kinds: Vec<Kind>,
+    row: usize,
+    end: usize,
+    batch_size: usize,
+    started: bool,
+}
+
+impl CalamineBatchIterator {
+    pub fn new(
+        py: Python<'_>,
+        range: Arc<Range<Data>>,
+        names: Vec<String>,
+        skip: usize,
+        options: BatchOptions,
+    ) -> PyResult<CalamineBatchIterator> {
+        let BatchOptions {
+            columns,
+            n_rows,
+            batch_size,
+            infer_schema_length,
+        } = options;
+        let positions = match columns {
+            Some(columns) => columns
+                .iter()
+                .map(|column| {
+                    names
+                        .iter()
+                        .position(|name| name == column)
+                        .ok_or_else(|| PyValueError::new_err(format!("Unknown column: '{column}'")))
+                })
+                .collect::<PyResult<Vec<_>>>()?,
+            None => (0..names.len()).collect(),
+        };
+        let batch_size = batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
+        if batch_size == 0 {
+            return Err(PyValueError::new_err("batch_size must be positive"));
+        }
+        let kinds = py.detach(|| column_kinds(&range, skip, infer_schema_length, &positions));
+        let row = skip.min(range.height());
+        let end = n_rows.map_or(range.height(), |n_rows| {
+            row.saturating_add(n_rows).min(range.height())
+        });
+        Ok(CalamineBatchIterator {
+            names: positions.iter().map(|&col| names[col].clone()).collect(),
+            range,
+            positions,
+            kinds,
+            row,
+            end,
+            batch_size,
+            started: false,
+        })
+    }
+}
+
+#[pymethods]
+impl CalamineBatchIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<CalamineArrowBatch> {
+        // an empty batch is returned for no rows, it has the schema
+        if slf.started && slf.row >= slf.end {
+            return None;
+        }
+        slf.started = true;
+        let start = slf.row;
+        let stop = start.saturating_add(slf.batch_size).min(slf.end);
+        slf.row = stop;
+        let this = &*slf;
+        let columns = slf.py().detach(|| {
+            this.positions
+                .iter()
+                .zip(&this.kinds)
+                .map(|(&col, &kind)| {
+                    let cells = this
+                        .range
+                        .rows()
+                        .skip(start)
+                        .take(stop - start)
+                        .map(|row| CellValue::from(&row[col]))
+                        .collect();
+                    Column::with_kind(kind, cells)
+                })
+                .collect()
+        });
+        Some(CalamineArrowBatch {
+            names: slf.names.clone(),
+            columns,
+        })
+    }
+}
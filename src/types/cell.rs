@@ -3,6 +3,7 @@ use std::convert::From;
 use calamine::DataType;
 use chrono::Datelike;
 use pyo3::prelude::*;
+use pyo3::types::PyList;
 
 /// https://docs.python.org/3/library/datetime.html#constants
 /// The smallest year number allowed in a date or datetime object. MINYEAR is 1.
@@ -124,3 +125,40 @@ where
         }
     }
 }
+
+/// The Python object of empty cells, `""` by default.
+#[derive(Default)]
+pub struct EmptyValue(Option<Py<PyAny>>);
+
+impl<'a, 'py> FromPyObject<'a, 'py> for EmptyValue {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        Ok(EmptyValue(Some(obj.to_owned().unbind())))
+    }
+}
+
+impl EmptyValue {
+    pub fn convert<'py>(&self, py: Python<'py>, value: CellValue) -> PyResult<Bound<'py, PyAny>> {
+        match (value, &self.0) {
+            (CellValue::Empty, Some(empty)) => Ok(empty.bind(py).clone()),
+            (value, _) => value.into_pyobject(py),
+        }
+    }
+
+    pub fn list<'py>(
+        &self,
+        py: Python<'py>,
+        values: impl ExactSizeIterator<Item = CellValue>,
+    ) -> PyResult<Bound<'py, PyList>> {
+        match &self.0 {
+            None => PyList::new(py, values),
+            Some(_) => PyList::new(
+                py,
+                values
+                    .map(|value| self.convert(py, value))
+                    .collect::<PyResult<Vec<_>>>()?,
+            ),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use calamine::{Data, Range};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike};
+
+use crate::CellValue;
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// Values of a sheet column, typed from the `CellValue` of its cells.
+/// Empty cells are `None`, a column with values of different types keeps the cells.
+#[derive(Clone)]
+pub enum Column {
+    Null(usize),
+    Int(Vec<Option<i64>>),
+    Float(Vec<Option<f64>>),
+    Bool(Vec<Option<bool>>),
+    String(Vec<Option<String>>),
+    Date(Vec<Option<NaiveDate>>),
+    DateTime(Vec<Option<NaiveDateTime>>),
+    Time(Vec<Option<NaiveTime>>),
+    Timedelta(Vec<Option<TimeDelta>>),
+    Mixed(Vec<CellValue>),
+}
+
+/// Type of a column, see `Column`.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Kind {
+    Null,
+    Int,
+    Float,
+    Bool,
+    String,
+    Date,
+    DateTime,
+    Time,
+    Timedelta,
+    Mixed,
+}
+
+impl Kind {
+    fn of(value: &CellValue) -> Kind {
+        match value {
+            CellValue::Empty => Kind::Null,
+            CellValue::Int(_) => Kind::Int,
+            CellValue::Float(_) => Kind::Float,
+            CellValue::Bool(_) => Kind::Bool,
+            CellValue::String(_) => Kind::String,
+            CellValue::Date(_) => Kind::Date,
+            CellValue::DateTime(_) => Kind::DateTime,
+            CellValue::Time(_) => Kind::Time,
+            CellValue::Timedelta(_) => Kind::Timedelta,
+        }
+    }
+
+    /// The kind of a column with values of both kinds, numbers and dates are widened.
+    fn merge(self, other: Kind) -> Kind {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Kind::Null, kind) | (kind, Kind::Null) => kind,
+            (Kind::Int, Kind::Float) | (Kind::Float, Kind::Int) => Kind::Float,
+            (Kind::Date, Kind::DateTime) | (Kind::DateTime, Kind::Date) => Kind::DateTime,
+            _ => Kind::Mixed,
+        }
+    }
+}
+
+impl Column {
+    pub fn from_cells(cells: Vec<CellValue>) -> Column {
+        let kind = cells
+            .iter()
+            .fold(Kind::Null, |kind, value| kind.merge(Kind::of(value)));
+        Column::with_kind(kind, cells)
+    }
+
+    /// Builds a column of a kind inferred from the cells or from a superset of them,
+    /// or from other cells of the column. Values of another kind are converted to strings
+    /// for a string column and are nulls otherwise.
+    pub fn with_kind(kind: Kind, cells: Vec<CellValue>) -> Column {
+        let cells = cells.into_iter();
+        match kind {
+            Kind::Null => Column::Null(cells.len()),
+            Kind::Int => Column::Int(
+                cells
+                    .map(|value| match value {
+                        CellValue::Int(v) => Some(v),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            Kind::Float => Column::Float(
+                cells
+                    .map(|value| match value {
+                        CellValue::Int(v) => Some(v as f64),
+                        CellValue::Float(v) => Some(v),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            Kind::Bool => Column::Bool(
+                cells
+                    .map(|value| match value {
+                        CellValue::Bool(v) => Some(v),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            Kind::Date => Column::Date(
+                cells
+                    .map(|value| match value {
+                        CellValue::Date(v) => Some(v),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            Kind::DateTime => Column::DateTime(
+                cells
+                    .map(|value| match value {
+                        CellValue::Date(v) => Some(v.and_time(NaiveTime::MIN)),
+                        CellValue::DateTime(v) => Some(v),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            Kind::Time => Column::Time(
+                cells
+                    .map(|value| match value {
+                        CellValue::Time(v) => Some(v),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            Kind::Timedelta => Column::Timedelta(
+                cells
+                    .map(|value| match value {
+                        CellValue::Timedelta(v) => Some(v),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            Kind::String => Column::String(cells.map(to_string).collect()),
+            Kind::Mixed => Column::Mixed(cells.collect()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Column::Null(len) => *len,
+            Column::Int(v) => v.len(),
+            Column::Float(v) => v.len(),
+            Column::Bool(v) => v.len(),
+            Column::String(v) => v.len(),
+            Column::Date(v) => v.len(),
+            Column::DateTime(v) => v.len(),
+            Column::Time(v) => v.len(),
+            Column::Timedelta(v) => v.len(),
+            Column::Mixed(v) => v.len(),
+        }
+    }
+}
+
+/// Reads the columns of the rows after `skip`.
+pub fn columns(range: &Range<Data>, skip: usize) -> Vec<Column> {
+    (0..range.width())
+        .map(|col| {
+            let cells = range
+                .rows()
+                .skip(skip)
+                .map(|row| CellValue::from(&row[col]))
+                .collect();
+            Column::from_cells(cells)
+        })
+        .collect()
+}
+
+/// Kinds of the columns at `positions` in the first `n_rows` rows after `skip`, all rows
+/// if `None`.
+pub fn column_kinds(
+    range: &Range<Data>,
+    skip: usize,
+    n_rows: Option<usize>,
+    positions: &[usize],
+) -> Vec<Kind> {
+    positions
+        .iter()
+        .map(|&col| {
+            range
+                .rows()
+                .skip(skip)
+                .take(n_rows.unwrap_or(usize::MAX))
+                .fold(Kind::Null, |kind, row| {
+                    kind.merge(Kind::of(&CellValue::from(&row[col])))
+                })
+        })
+        .collect()
+}
+
+/// Formats a float as `repr()` does, with the shortest digits which read back the same
+/// value and an exponent out of `1e-4..1e16`, e.g. `1e+16` or `1.5e-05`.
+fn float_repr(v: f64) -> String {
+    if v.is_nan() {
+        return "nan".to_string();
+    }
+    if v.is_infinite() {
+        return if v > 0.0 { "inf" } else { "-inf" }.to_string();
+    }
+    let scientific = format!("{v:e}");
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    match exponent.parse::<i32>().unwrap_or_default() {
+        // the same rule as Rust's `Debug`, which adds `.0` to integral values
+        -4..=15 => format!("{v:?}"),
+        exponent if exponent < 0 => format!("{mantissa}e-{:02}", -exponent),
+        exponent => format!("{mantissa}e+{exponent:02}"),
+    }
+}
+
+/// Formats a value as `str()` does for the Python object of the cell.
+pub fn to_string(value: CellValue) -> Option<String> {
+    Some(match value {
+        CellValue::Empty => return None,
+        CellValue::String(v) => v,
+        CellValue::Int(v) => v.to_string(),
+        CellValue::Float(v) => float_repr(v),
+        CellValue::Bool(true) => "True".to_string(),
+        CellValue::Bool(false) => "False".to_string(),
+        CellValue::Date(v) => v.to_string(),
+        CellValue::DateTime(v) => v.to_string(),
+        CellValue::Time(v) if v.nanosecond() == 0 => v.format("%H:%M:%S").to_string(),
+        CellValue::Time(v) => v.format("%H:%M:%S%.6f").to_string(),
+        CellValue::Timedelta(v) => {
+            // days are rounded down, `-1 day, 23:59:59` for a negative second
+            let micros = v.num_microseconds().unwrap_or_default();
+            let days = micros.div_euclid(MICROS_PER_DAY);
+            let time = NaiveTime::MIN + TimeDelta::microseconds(micros.rem_euclid(MICROS_PER_DAY));
+            let time = if time.nanosecond() == 0 {
+                time.format("%-H:%M:%S").to_string()
+            } else {
+                time.format("%-H:%M:%S%.6f").to_string()
+            };
+            match days {
+                0 => time,
+                1 | -1 => format!("{days} day, {time}"),
+                _ => format!("{days} days, {time}"),
+            }
+        }
+    })
+}
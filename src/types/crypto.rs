@@ -0,0 +1,825 @@
+//! Decryption of password-protected workbooks, see [MS-OFFCRYPTO], [MS-XLS 2.2.10]
+//! and [ODF 1.2 part 3, 3.8].
+
+use std::collections::HashMap;
+use std::io::{self, Cursor, Read, Seek, SeekFrom, Write};
+
+use aes::cipher::block_padding::NoPadding;
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{
+    AsyncStreamCipher, BlockDecrypt, BlockDecryptMut, InnerIvInit, KeyInit, KeyIvInit,
+};
+use base64::Engine;
+use calamine::Error as CalamineCrateError;
+use cfb::CompoundFile;
+use flate2::read::DeflateDecoder;
+use md5::Md5;
+use quick_xml::encoding::Decoder;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::XmlVersion;
+use sha1::{Digest, Sha1};
+use sha2::{Sha256, Sha384, Sha512};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::types::format::{CFB_SIGNATURE, ZIP_SIGNATURE};
+use crate::Error;
+
+/// [MS-OFFCRYPTO 2.3.4.13] block keys of the agile encryption
+const BLOCK_KEY_VERIFIER_INPUT: [u8; 8] = [0xfe, 0xa7, 0xd2, 0x76, 0x3b, 0x4b, 0x9e, 0x79];
+const BLOCK_KEY_VERIFIER_VALUE: [u8; 8] = [0xd7, 0xaa, 0x0f, 0x6d, 0x30, 0x61, 0x34, 0x4e];
+const BLOCK_KEY_ENCRYPTED_KEY: [u8; 8] = [0x14, 0x6e, 0x0b, 0xe7, 0xab, 0xac, 0xd0, 0xd6];
+const AGILE_SEGMENT_LENGTH: usize = 4096;
+const STANDARD_SPIN_COUNT: u32 = 50000;
+/// Maximum spinCount of agile encryption [MS-OFFCRYPTO 2.3.4.10]
+const MAX_SPIN_COUNT: usize = 10_000_000;
+const XLS_BLOCK_LENGTH: usize = 1024;
+
+fn invalid_data(message: &str) -> Error {
+    Error::Calamine(CalamineCrateError::Io(io::Error::new(
+        io::ErrorKind::InvalidData,
+        message.to_string(),
+    )))
+}
+
+fn read_u16(data: &[u8]) -> u16 {
+    u16::from_le_bytes([data[0], data[1]])
+}
+
+fn read_u32(data: &[u8]) -> u32 {
+    u32::from_le_bytes([data[0], data[1], data[2], data[3]])
+}
+
+/// Takes `len` bytes at `offset`, fails on truncated structures.
+fn slice(data: &[u8], offset: usize, len: usize) -> Result<&[u8], Error> {
+    data.get(offset..offset + len)
+        .ok_or_else(|| invalid_data("truncated encryption info"))
+}
+
+/// Decrypts a password-protected workbook into an unencrypted one.
+///
+/// Returns `None` if the workbook isn't encrypted, so it can be read as is.
+pub fn decrypt<RS: Read + Seek>(mut reader: RS, password: &str) -> Result<Option<Vec<u8>>, Error> {
+    let mut signature = [0u8; 8];
+    reader.seek(SeekFrom::Start(0))?;
+    if reader.read_exact(&mut signature).is_err() {
+        return Ok(None);
+    }
+    reader.seek(SeekFrom::Start(0))?;
+    if signature.starts_with(&ZIP_SIGNATURE) {
+        return decrypt_odf(reader, password);
+    }
+    if signature != CFB_SIGNATURE {
+        return Ok(None);
+    }
+
+    let mut cfb = CompoundFile::open(reader)?;
+    if cfb.is_stream("EncryptionInfo") && cfb.is_stream("EncryptedPackage") {
+        let mut info = Vec::new();
+        cfb.open_stream("EncryptionInfo")?.read_to_end(&mut info)?;
+        let mut package = Vec::new();
+        cfb.open_stream("EncryptedPackage")?
+            .read_to_end(&mut package)?;
+        return decrypt_ooxml(&info, &package, password).map(Some);
+    }
+
+    if cfb.is_stream("Workbook") {
+        let mut stream = Vec::new();
+        cfb.open_stream("Workbook")?.read_to_end(&mut stream)?;
+        if !decrypt_xls_stream(&mut stream, password)? {
+            return Ok(None);
+        }
+        // Rewrite the stream in place, it has the same size
+        let mut reader = cfb.into_inner();
+        let mut buf = Vec::new();
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_to_end(&mut buf)?;
+        let mut cfb = CompoundFile::open(Cursor::new(buf))?;
+        {
+            let mut workbook = cfb.open_stream("Workbook")?;
+            workbook.write_all(&stream)?;
+            workbook.flush()?;
+        }
+        cfb.flush()?;
+        return Ok(Some(cfb.into_inner().into_inner()));
+    }
+
+    Ok(None)
+}
+
+fn decrypt_ooxml(info: &[u8], package: &[u8], password: &str) -> Result<Vec<u8>, Error> {
+    let version = (read_u16(slice(info, 0, 2)?), read_u16(slice(info, 2, 2)?));
+    let mut data = match version {
+        (4, 4) => decrypt_agile(info, package, password)?,
+        (2..=4, 2) => decrypt_standard(info, package, password)?,
+        (major, minor) => {
+            return Err(Error::UnsupportedEncryption(format!(
+                "encryption version {major}.{minor}"
+            )))
+        }
+    };
+    let size = u64::from_le_bytes(slice(package, 0, 8)?.try_into().unwrap()) as usize;
+    if size > data.len() {
+        return Err(invalid_data("truncated encrypted package"));
+    }
+    data.truncate(size);
+    Ok(data)
+}
+
+fn password_utf16(password: &str) -> Vec<u8> {
+    password.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+#[derive(Clone, Copy)]
+enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    fn from_name(name: &str) -> Result<Self, Error> {
+        match name {
+            "SHA1" => Ok(HashAlgorithm::Sha1),
+            "SHA256" => Ok(HashAlgorithm::Sha256),
+            "SHA384" => Ok(HashAlgorithm::Sha384),
+            "SHA512" => Ok(HashAlgorithm::Sha512),
+            _ => Err(Error::UnsupportedEncryption(format!(
+                "hash algorithm {name}"
+            ))),
+        }
+    }
+
+    fn hash(&self, parts: &[&[u8]]) -> Vec<u8> {
+        fn hash<D: Digest>(parts: &[&[u8]]) -> Vec<u8> {
+            let mut hasher = D::new();
+            for part in parts {
+                hasher.update(part);
+            }
+            hasher.finalize().to_vec()
+        }
+        match self {
+            HashAlgorithm::Sha1 => hash::<Sha1>(parts),
+            HashAlgorithm::Sha256 => hash::<Sha256>(parts),
+            HashAlgorithm::Sha384 => hash::<Sha384>(parts),
+            HashAlgorithm::Sha512 => hash::<Sha512>(parts),
+        }
+    }
+}
+
+fn aes_cbc_decrypt(key: &[u8], iv: &[u8], data: &mut [u8]) -> Result<(), Error> {
+    fn decrypt<C: BlockDecryptMut + KeyIvInit>(
+        key: &[u8],
+        iv: &[u8],
+        data: &mut [u8],
+    ) -> Result<(), Error> {
+        C::new_from_slices(key, iv)
+            .map_err(|_| invalid_data("invalid key or IV length"))?
+            .decrypt_padded_mut::<NoPadding>(data)
+            .map_err(|_| invalid_data("encrypted data isn't aligned to the block size"))?;
+        Ok(())
+    }
+    match key.len() {
+        16 => decrypt::<cbc::Decryptor<aes::Aes128>>(key, iv, data),
+        24 => decrypt::<cbc::Decryptor<aes::Aes192>>(key, iv, data),
+        32 => decrypt::<cbc::Decryptor<aes::Aes256>>(key, iv, data),
+        _ => Err(Error::UnsupportedEncryption(format!(
+            "AES key size {}",
+            key.len() * 8
+        ))),
+    }
+}
+
+fn aes_ecb_decrypt(key: &[u8], data: &mut [u8]) -> Result<(), Error> {
+    fn decrypt<C: BlockDecrypt + KeyInit>(key: &[u8], data: &mut [u8]) -> Result<(), Error> {
+        let cipher = C::new_from_slice(key).map_err(|_| invalid_data("invalid key length"))?;
+        for block in data.chunks_exact_mut(16) {
+            cipher.decrypt_block(GenericArray::from_mut_slice(block));
+        }
+        Ok(())
+    }
+    match key.len() {
+        16 => decrypt::<aes::Aes128>(key, data),
+        24 => decrypt::<aes::Aes192>(key, data),
+        32 => decrypt::<aes::Aes256>(key, data),
+        _ => Err(Error::UnsupportedEncryption(format!(
+            "AES key size {}",
+            key.len() * 8
+        ))),
+    }
+}
+
+/// Pads or truncates to `len` bytes with 0x36 [MS-OFFCRYPTO 2.3.4.11].
+fn fit(mut data: Vec<u8>, len: usize) -> Vec<u8> {
+    data.resize(len, 0x36);
+    data
+}
+
+/// Attributes of the `keyData` and password `encryptedKey` elements.
+struct AgileInfo {
+    key_data: HashMap<String, String>,
+    encrypted_key: HashMap<String, String>,
+}
+
+impl AgileInfo {
+    fn parse(xml: &[u8]) -> Result<Self, Error> {
+        let mut reader = quick_xml::Reader::from_reader(xml);
+        let mut buf = Vec::new();
+        let mut key_data = None;
+        let mut encrypted_key = None;
+        loop {
+            let decoder = reader.decoder();
+            let event = reader
+                .read_event_into(&mut buf)
+                .map_err(|e| invalid_data(&e.to_string()))?;
+            match event {
+                Event::Start(e) | Event::Empty(e) => {
+                    match e.local_name().as_ref() {
+                        b"keyData" => key_data = Some(attributes(&e, decoder)?),
+                        // the password key encryptor, certificate ones are not supported
+                        b"encryptedKey" if encrypted_key.is_none() => {
+                            let attrs = attributes(&e, decoder)?;
+                            if attrs.contains_key("encryptedKeyValue")
+                                && attrs.contains_key("spinCount")
+                            {
+                                encrypted_key = Some(attrs);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        match (key_data, encrypted_key) {
+            (Some(key_data), Some(encrypted_key)) => Ok(AgileInfo {
+                key_data,
+                encrypted_key,
+            }),
+            _ => Err(Error::UnsupportedEncryption(
+                "no password key encryptor".to_string(),
+            )),
+        }
+    }
+}
+
+/// Collects the attributes of an element by their local names.
+fn attributes(e: &BytesStart, decoder: Decoder) -> Result<HashMap<String, String>, Error> {
+    e.attributes()
+        .map(|a| {
+            let a = a.map_err(|e| invalid_data(&e.to_string()))?;
+            let value = a
+                .decoded_and_normalized_value(XmlVersion::Implicit1_0, decoder)
+                .map_err(|e| invalid_data(&e.to_string()))?;
+            Ok((
+                String::from_utf8_lossy(a.key.local_name().as_ref()).into_owned(),
+                value.into_owned(),
+            ))
+        })
+        .collect()
+}
+
+fn attr<'a>(attributes: &'a HashMap<String, String>, name: &str) -> Result<&'a str, Error> {
+    attributes
+        .get(name)
+        .map(|s| s.as_str())
+        .ok_or_else(|| invalid_data(&format!("missing {name} in encryption info")))
+}
+
+fn attr_usize(attributes: &HashMap<String, String>, name: &str) -> Result<usize, Error> {
+    attr(attributes, name)?
+        .parse()
+        .map_err(|_| invalid_data(&format!("invalid {name} in encryption info")))
+}
+
+fn attr_base64(attributes: &HashMap<String, String>, name: &str) -> Result<Vec<u8>, Error> {
+    base64::engine::general_purpose::STANDARD
+        .decode(attr(attributes, name)?)
+        .map_err(|_| invalid_data(&format!("invalid {name} in encryption info")))
+}
+
+fn check_cipher(attributes: &HashMap<String, String>) -> Result<(), Error> {
+    match (
+        attr(attributes, "cipherAlgorithm")?,
+        attr(attributes, "cipherChaining")?,
+    ) {
+        ("AES", "ChainingModeCBC") => Ok(()),
+        (algorithm, chaining) => Err(Error::UnsupportedEncryption(format!(
+            "cipher {algorithm} {chaining}"
+        ))),
+    }
+}
+
+/// [MS-OFFCRYPTO 2.3.4.10] Agile encryption
+fn decrypt_agile(info: &[u8], package: &[u8], password: &str) -> Result<Vec<u8>, Error> {
+    let AgileInfo {
+        key_data,
+        encrypted_key,
+    } = AgileInfo::parse(info.get(8..).unwrap_or_default())?;
+    check_cipher(&key_data)?;
+    check_cipher(&encrypted_key)?;
+
+    // [MS-OFFCRYPTO 2.3.4.11] key derivation from the password
+    let hash = HashAlgorithm::from_name(attr(&encrypted_key, "hashAlgorithm")?)?;
+    let salt = attr_base64(&encrypted_key, "saltValue")?;
+    let key_len = attr_usize(&encrypted_key, "keyBits")? / 8;
+    let block_size = attr_usize(&encrypted_key, "blockSize")?;
+    let spin_count = match attr_usize(&encrypted_key, "spinCount")? {
+        spin_count @ 0..=MAX_SPIN_COUNT => spin_count as u32,
+        spin_count => {
+            return Err(Error::UnsupportedEncryption(format!(
+                "spin count {spin_count}"
+            )))
+        }
+    };
+    let mut h = hash.hash(&[&salt, &password_utf16(password)]);
+    for i in 0..spin_count {
+        h = hash.hash(&[&i.to_le_bytes(), &h]);
+    }
+    let iv = fit(salt, block_size);
+    let decrypt_value = |block_key: &[u8], name: &str| -> Result<Vec<u8>, Error> {
+        let key = fit(hash.hash(&[&h, block_key]), key_len);
+        let mut value = attr_base64(&encrypted_key, name)?;
+        aes_cbc_decrypt(&key, &iv, &mut value)?;
+        Ok(value)
+    };
+
+    // [MS-OFFCRYPTO 2.3.4.13] password verification
+    let mut verifier_input =
+        decrypt_value(&BLOCK_KEY_VERIFIER_INPUT, "encryptedVerifierHashInput")?;
+    verifier_input.truncate(attr_usize(&encrypted_key, "saltSize")?);
+    let verifier_hash = decrypt_value(&BLOCK_KEY_VERIFIER_VALUE, "encryptedVerifierHashValue")?;
+    let hash_size = attr_usize(&encrypted_key, "hashSize")?;
+    if verifier_hash.get(..hash_size) != Some(hash.hash(&[&verifier_input]).as_slice()) {
+        return Err(Error::InvalidPassword);
+    }
+    let mut secret_key = decrypt_value(&BLOCK_KEY_ENCRYPTED_KEY, "encryptedKeyValue")?;
+    secret_key.truncate(key_len);
+
+    // [MS-OFFCRYPTO 2.3.4.15] the package is encrypted in segments with their own IV
+    let hash = HashAlgorithm::from_name(attr(&key_data, "hashAlgorithm")?)?;
+    let salt = attr_base64(&key_data, "saltValue")?;
+    let block_size = attr_usize(&key_data, "blockSize")?;
+    let mut data = package.get(8..).unwrap_or_default().to_vec();
+    for (index, segment) in data.chunks_mut(AGILE_SEGMENT_LENGTH).enumerate() {
+        let iv = fit(
+            hash.hash(&[&salt, &(index as u32).to_le_bytes()]),
+            block_size,
+        );
+        let len = segment.len() - segment.len() % block_size;
+        aes_cbc_decrypt(&secret_key, &iv, &mut segment[..len])?;
+    }
+    Ok(data)
+}
+
+/// [MS-OFFCRYPTO 2.3.4.5] Standard encryption
+fn decrypt_standard(info: &[u8], package: &[u8], password: &str) -> Result<Vec<u8>, Error> {
+    let flags = read_u32(slice(info, 4, 4)?);
+    // fCryptoAPI and fAES must be set, fExternal must not
+    if flags & 0x24 != 0x24 || flags & 0x10 != 0 {
+        return Err(Error::UnsupportedEncryption(format!(
+            "standard encryption flags {flags:#x}"
+        )));
+    }
+    let header_size = read_u32(slice(info, 8, 4)?) as usize;
+    let header = slice(info, 12, header_size)?;
+    let key_len = read_u32(slice(header, 16, 4)?) as usize / 8;
+
+    // EncryptionVerifier [MS-OFFCRYPTO 2.3.3]
+    let verifier = info.get(12 + header_size..).unwrap_or_default();
+    let salt_size = read_u32(slice(verifier, 0, 4)?) as usize;
+    let salt = slice(verifier, 4, salt_size)?;
+    let mut encrypted_verifier = slice(verifier, 4 + salt_size, 16)?.to_vec();
+    let hash_size = read_u32(slice(verifier, 20 + salt_size, 4)?) as usize;
+    let mut encrypted_verifier_hash = slice(verifier, 24 + salt_size, 32)?.to_vec();
+
+    // [MS-OFFCRYPTO 2.3.4.7] key derivation from the password
+    let mut h = Sha1::new()
+        .chain_update(salt)
+        .chain_update(password_utf16(password))
+        .finalize();
+    for i in 0..STANDARD_SPIN_COUNT {
+        h = Sha1::new()
+            .chain_update(i.to_le_bytes())
+            .chain_update(h)
+            .finalize();
+    }
+    let h = Sha1::new()
+        .chain_update(h)
+        .chain_update(0u32.to_le_bytes())
+        .finalize();
+    let derive = |fill: u8| {
+        let mut buf = [fill; 64];
+        buf.iter_mut().zip(h.iter()).for_each(|(b, h)| *b ^= h);
+        Sha1::digest(buf)
+    };
+    let mut key = derive(0x36).to_vec();
+    key.extend_from_slice(&derive(0x5c));
+    key.truncate(key_len);
+
+    // [MS-OFFCRYPTO 2.3.4.9] password verification
+    aes_ecb_decrypt(&key, &mut encrypted_verifier)?;
+    aes_ecb_decrypt(&key, &mut encrypted_verifier_hash)?;
+    if encrypted_verifier_hash.get(..hash_size)
+        != Some(Sha1::digest(&encrypted_verifier).as_slice())
+    {
+        return Err(Error::InvalidPassword);
+    }
+
+    let mut data = package.get(8..).unwrap_or_default().to_vec();
+    aes_ecb_decrypt(&key, &mut data)?;
+    Ok(data)
+}
+
+struct Rc4 {
+    state: [u8; 256],
+    i: u8,
+    j: u8,
+}
+
+impl Rc4 {
+    fn new(key: &[u8]) -> Self {
+        let mut state = [0u8; 256];
+        state.iter_mut().enumerate().for_each(|(i, s)| *s = i as u8);
+        let mut j = 0u8;
+        for i in 0..256 {
+            j = j.wrapping_add(state[i]).wrapping_add(key[i % key.len()]);
+            state.swap(i, j as usize);
+        }
+        Rc4 { state, i: 0, j: 0 }
+    }
+
+    fn apply(&mut self, data: &mut [u8]) {
+        for byte in data {
+            self.i = self.i.wrapping_add(1);
+            self.j = self.j.wrapping_add(self.state[self.i as usize]);
+            self.state.swap(self.i as usize, self.j as usize);
+            let k = self.state[self.i as usize].wrapping_add(self.state[self.j as usize]);
+            *byte ^= self.state[k as usize];
+        }
+    }
+}
+
+/// Key derivation of the XLS RC4 encryptions, keys change every 1024 bytes of the stream.
+enum Rc4Key {
+    /// [MS-OFFCRYPTO 2.3.6.2]
+    Md5 { truncated_hash: Vec<u8> },
+    /// [MS-OFFCRYPTO 2.3.5.2]
+    CryptoApi { hash: Vec<u8>, key_len: usize },
+}
+
+impl Rc4Key {
+    fn cipher(&self, block: u32) -> Rc4 {
+        match self {
+            Rc4Key::Md5 { truncated_hash } => Rc4::new(
+                &Md5::new()
+                    .chain_update(truncated_hash)
+                    .chain_update(block.to_le_bytes())
+                    .finalize(),
+            ),
+            Rc4Key::CryptoApi { hash, key_len } => {
+                let mut key = Sha1::new()
+                    .chain_update(hash)
+                    .chain_update(block.to_le_bytes())
+                    .finalize()[..*key_len]
+                    .to_vec();
+                // 40-bit keys are padded to 128 bits
+                if *key_len == 5 {
+                    key.resize(16, 0);
+                }
+                Rc4::new(&key)
+            }
+        }
+    }
+
+    /// Parses the encryption header of the FilePass record [MS-XLS 2.4.117]
+    /// and verifies the password.
+    fn from_filepass(filepass: &[u8], password: &str) -> Result<Self, Error> {
+        let password = password_utf16(password);
+        match (
+            read_u16(slice(filepass, 0, 2)?),
+            read_u16(slice(filepass, 2, 2)?),
+        ) {
+            (0, _) => Err(Error::UnsupportedEncryption("XOR obfuscation".to_string())),
+            (1, 1) => {
+                let salt = slice(filepass, 6, 16)?;
+                let h = Md5::digest(&password);
+                let mut buf = Vec::with_capacity(336);
+                for _ in 0..16 {
+                    buf.extend_from_slice(&h[..5]);
+                    buf.extend_from_slice(salt);
+                }
+                let key = Rc4Key::Md5 {
+                    truncated_hash: Md5::digest(&buf)[..5].to_vec(),
+                };
+                let mut verifier = slice(filepass, 22, 32)?.to_vec();
+                key.cipher(0).apply(&mut verifier);
+                if Md5::digest(&verifier[..16]).as_slice() != &verifier[16..] {
+                    return Err(Error::InvalidPassword);
+                }
+                Ok(key)
+            }
+            (1, _) => {
+                // EncryptionHeader and EncryptionVerifier of the RC4 CryptoAPI encryption
+                let header_size = read_u32(slice(filepass, 10, 4)?) as usize;
+                let header = slice(filepass, 14, header_size)?;
+                let key_len = match read_u32(slice(header, 16, 4)?) as usize / 8 {
+                    0 => 5,
+                    key_len => key_len,
+                };
+                let verifier = filepass.get(14 + header_size..).unwrap_or_default();
+                let salt_size = read_u32(slice(verifier, 0, 4)?) as usize;
+                let salt = slice(verifier, 4, salt_size)?;
+                let key = Rc4Key::CryptoApi {
+                    hash: Sha1::new()
+                        .chain_update(salt)
+                        .chain_update(&password)
+                        .finalize()
+                        .to_vec(),
+                    key_len,
+                };
+                let mut data = slice(verifier, 4 + salt_size, 16)?.to_vec();
+                let hash_size = read_u32(slice(verifier, 20 + salt_size, 4)?) as usize;
+                data.extend_from_slice(slice(verifier, 24 + salt_size, hash_size)?);
+                key.cipher(0).apply(&mut data);
+                if Sha1::digest(&data[..16]).as_slice() != &data[16..] {
+                    return Err(Error::InvalidPassword);
+                }
+                Ok(key)
+            }
+            (typ, _) => Err(Error::UnsupportedEncryption(format!(
+                "encryption type {typ}"
+            ))),
+        }
+    }
+}
+
+/// Decrypts a BIFF8 workbook stream in place, returns `false` if it isn't encrypted.
+fn decrypt_xls_stream(stream: &mut [u8], password: &str) -> Result<bool, Error> {
+    // FilePass [MS-XLS 2.4.117] follows the BOF of the globals substream
+    let bof_len = read_u16(slice(stream, 2, 2)?) as usize;
+    let filepass_offset = 4 + bof_len;
+    if stream.len() < filepass_offset + 4 || read_u16(&stream[filepass_offset..]) != 0x002F {
+        return Ok(false);
+    }
+    let filepass_len = read_u16(&stream[filepass_offset + 2..]) as usize;
+    let filepass = slice(stream, filepass_offset + 4, filepass_len)?;
+    let key = Rc4Key::from_filepass(filepass, password)?;
+
+    // The key stream runs over the whole stream, but only record data is encrypted
+    let mut key_stream = vec![0u8; stream.len()];
+    for (block, chunk) in key_stream.chunks_mut(XLS_BLOCK_LENGTH).enumerate() {
+        key.cipher(block as u32).apply(chunk);
+    }
+
+    let mut offset = 0;
+    while offset + 4 <= stream.len() {
+        let typ = read_u16(&stream[offset..]);
+        let len = read_u16(&stream[offset + 2..]) as usize;
+        let start = offset + 4;
+        let end = (start + len).min(stream.len());
+        let skip = match typ {
+            // BOF, FilePass, UsrExcl, FileLock, InterfaceHdr, RRDInfo, RRDHead are not encrypted
+            0x0809 | 0x002F | 0x0194 | 0x0195 | 0x00E1 | 0x0196 | 0x0138 => len,
+            // lbPlyPos of BoundSheet8 is not encrypted
+            0x0085 => 4,
+            _ => 0,
+        };
+        for i in (start + skip).min(end)..end {
+            stream[i] ^= key_stream[i];
+        }
+        offset = start + len;
+    }
+
+    // calamine rejects FilePass, replace it by an unknown record of the same size,
+    // stream offsets (BoundSheet8, ExtSST, ...) must stay the same
+    stream[filepass_offset..filepass_offset + 2].copy_from_slice(&0u16.to_le_bytes());
+    Ok(true)
+}
+
+/// Encrypted entries of an OpenDocument manifest [ODF 1.2 part 3, 3.8], with the
+/// manifest rewritten without their `encryption-data`.
+struct OdfManifest {
+    /// Attributes of `file-entry` and its `encryption-data` children, keyed by `element:name`
+    entries: HashMap<String, HashMap<String, String>>,
+    manifest: Vec<u8>,
+}
+
+impl OdfManifest {
+    fn parse(xml: &[u8]) -> Result<Self, Error> {
+        let mut reader = quick_xml::Reader::from_reader(xml);
+        let mut writer = quick_xml::Writer::new(Vec::new());
+        let mut buf = Vec::new();
+        let mut entries = HashMap::new();
+        let mut entry: Option<HashMap<String, String>> = None;
+        // depth inside a skipped encryption-data element
+        let mut depth = 0;
+        loop {
+            buf.clear();
+            let decoder = reader.decoder();
+            let event = reader
+                .read_event_into(&mut buf)
+                .map_err(|e| invalid_data(&e.to_string()))?;
+            match &event {
+                Event::Start(e) | Event::Empty(e) => {
+                    let element = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                    if element == "file-entry" {
+                        entry = Some(HashMap::new());
+                    }
+                    if let Some(entry) = entry.as_mut() {
+                        for (name, value) in attributes(e, decoder)? {
+                            entry.insert(format!("{element}:{name}"), value);
+                        }
+                    }
+                    if depth > 0 || element == "encryption-data" {
+                        if matches!(event, Event::Start(_)) {
+                            depth += 1;
+                        }
+                        continue;
+                    }
+                    if element == "file-entry" && matches!(event, Event::Empty(_)) {
+                        add_odf_entry(&mut entries, entry.take());
+                    }
+                }
+                Event::End(_) if depth > 0 => {
+                    depth -= 1;
+                    continue;
+                }
+                Event::End(e) if e.local_name().as_ref() == b"file-entry" => {
+                    add_odf_entry(&mut entries, entry.take());
+                }
+                Event::Eof => break,
+                _ if depth > 0 => continue,
+                _ => {}
+            }
+            writer.write_event(event)?;
+        }
+        Ok(OdfManifest {
+            entries,
+            manifest: writer.into_inner(),
+        })
+    }
+}
+
+fn add_odf_entry(
+    entries: &mut HashMap<String, HashMap<String, String>>,
+    entry: Option<HashMap<String, String>>,
+) {
+    if let Some(entry) = entry {
+        if entry.contains_key("encryption-data:checksum-type")
+            || entry.contains_key("algorithm:algorithm-name")
+        {
+            if let Some(path) = entry.get("file-entry:full-path") {
+                entries.insert(path.clone(), entry);
+            }
+        }
+    }
+}
+
+/// Decrypts an encrypted OpenDocument package into an unencrypted one.
+///
+/// Returns `None` if the package has no encrypted entries.
+fn decrypt_odf<RS: Read + Seek>(reader: RS, password: &str) -> Result<Option<Vec<u8>>, Error> {
+    let mut zip = ZipArchive::new(reader).map_err(io::Error::from)?;
+    let mut manifest = Vec::new();
+    match zip.by_name("META-INF/manifest.xml") {
+        Ok(mut file) => file.read_to_end(&mut manifest)?,
+        Err(_) => return Ok(None),
+    };
+    let OdfManifest { entries, manifest } = OdfManifest::parse(&manifest)?;
+    if entries.is_empty() {
+        return Ok(None);
+    }
+
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for i in 0..zip.len() {
+        let file = zip.by_index_raw(i).map_err(io::Error::from)?;
+        let name = file.name().to_string();
+        if name == "META-INF/manifest.xml" {
+            drop(file);
+            writer.start_file(name, deflated).map_err(io::Error::from)?;
+            writer.write_all(&manifest)?;
+        } else if let Some(entry) = entries.get(&name) {
+            drop(file);
+            let mut data = Vec::new();
+            zip.by_index(i)
+                .map_err(io::Error::from)?
+                .read_to_end(&mut data)?;
+            let data = decrypt_odf_entry(data, entry, password)?;
+            writer.start_file(name, deflated).map_err(io::Error::from)?;
+            writer.write_all(&data)?;
+        } else {
+            writer.raw_copy_file(file).map_err(io::Error::from)?;
+        }
+    }
+    Ok(Some(writer.finish().map_err(io::Error::from)?.into_inner()))
+}
+
+/// Decrypts and inflates an entry of an OpenDocument package.
+fn decrypt_odf_entry(
+    mut data: Vec<u8>,
+    entry: &HashMap<String, String>,
+    password: &str,
+) -> Result<Vec<u8>, Error> {
+    let start_key = match entry
+        .get("start-key-generation:start-key-generation-name")
+        .map(String::as_str)
+    {
+        None | Some("SHA1" | "http://www.w3.org/2000/09/xmldsig#sha1") => {
+            Sha1::digest(password).to_vec()
+        }
+        Some(
+            "SHA256"
+            | "http://www.w3.org/2000/09/xmldsig#sha256"
+            | "http://www.w3.org/2001/04/xmlenc#sha256",
+        ) => Sha256::digest(password).to_vec(),
+        Some(name) => {
+            return Err(Error::UnsupportedEncryption(format!(
+                "start key generation {name}"
+            )))
+        }
+    };
+
+    match attr(entry, "key-derivation:key-derivation-name")? {
+        "PBKDF2" => {}
+        name => {
+            return Err(Error::UnsupportedEncryption(format!(
+                "key derivation {name}"
+            )))
+        }
+    }
+    let key_size = match entry.get("key-derivation:key-size") {
+        Some(_) => attr_usize(entry, "key-derivation:key-size")?,
+        None => 16,
+    };
+    let mut key = vec![0u8; key_size];
+    pbkdf2::pbkdf2_hmac::<Sha1>(
+        &start_key,
+        &attr_base64(entry, "key-derivation:salt")?,
+        attr_usize(entry, "key-derivation:iteration-count")? as u32,
+        &mut key,
+    );
+
+    let iv = attr_base64(entry, "algorithm:initialisation-vector")?;
+    match attr(entry, "algorithm:algorithm-name")? {
+        "Blowfish CFB" => {
+            let cipher = blowfish::Blowfish::new_from_slice(&key)
+                .map_err(|_| invalid_data("invalid key length"))?;
+            cfb_mode::Decryptor::<blowfish::Blowfish>::inner_iv_slice_init(cipher, &iv)
+                .map_err(|_| invalid_data("invalid IV length"))?
+                .decrypt(&mut data);
+        }
+        "http://www.w3.org/2001/04/xmlenc#aes128-cbc"
+        | "http://www.w3.org/2001/04/xmlenc#aes192-cbc"
+        | "http://www.w3.org/2001/04/xmlenc#aes256-cbc" => {
+            aes_cbc_decrypt(&key, &iv, &mut data)?;
+            // W3C XML encryption padding, the last byte is the padding length
+            match data.last() {
+                Some(&pad) if (1..=16).contains(&pad) && pad as usize <= data.len() => {
+                    data.truncate(data.len() - pad as usize);
+                }
+                _ => return Err(Error::InvalidPassword),
+            }
+        }
+        name => {
+            return Err(Error::UnsupportedEncryption(format!("algorithm {name}")));
+        }
+    }
+
+    // The checksum is computed over the first kilobyte of the decrypted, still compressed data
+    if let Some(checksum_type) = entry.get("encryption-data:checksum-type") {
+        let head = &data[..data.len().min(1024)];
+        let digest = match checksum_type.as_str() {
+            "SHA1/1K" | "urn:oasis:names:tc:opendocument:xmlns:manifest:1.0#sha1-1k" => {
+                Sha1::digest(head).to_vec()
+            }
+            "urn:oasis:names:tc:opendocument:xmlns:manifest:1.0#sha256-1k" => {
+                Sha256::digest(head).to_vec()
+            }
+            name => {
+                return Err(Error::UnsupportedEncryption(format!("checksum {name}")));
+            }
+        };
+        if digest != attr_base64(entry, "encryption-data:checksum")? {
+            return Err(Error::InvalidPassword);
+        }
+    }
+
+    // Entries are deflated before encryption, the size is the one of the inflated data.
+    // Without a checksum, data that does not inflate is the only sign of a wrong password.
+    let mut inflated = Vec::new();
+    match DeflateDecoder::new(data.as_slice()).read_to_end(&mut inflated) {
+        Ok(_)
+            if entry
+                .get("file-entry:size")
+                .is_none_or(|size| size.parse() == Ok(inflated.len())) =>
+        {
+            Ok(inflated)
+        }
+        _ => Err(Error::InvalidPassword),
+    }
+}
@@ -0,0 +1,152 @@
+use pyo3::prelude::*;
+
+#[pyclass(from_py_object)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DefinedName {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    scope: Option<String>,
+    #[pyo3(get)]
+    formula: String,
+}
+
+#[pymethods]
+impl DefinedName {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "DefinedName(name='{}', scope={}, formula='{}')",
+            self.name,
+            self.scope
+                .as_ref()
+                .map_or("None".to_string(), |v| format!("'{v}'")),
+            self.formula,
+        ))
+    }
+}
+
+impl DefinedName {
+    pub fn new(name: String, scope: Option<String>, formula: String) -> Self {
+        DefinedName {
+            name,
+            scope,
+            formula,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn scope(&self) -> Option<&str> {
+        self.scope.as_deref()
+    }
+
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+}
+
+/// Last row and column of a worksheet, the end of whole columns and rows.
+const MAX_ROW: u32 = 1_048_575;
+const MAX_COLUMN: u32 = 16_383;
+
+/// A single cell or rectangular area of a sheet, positions are zero-based (row, column).
+#[derive(Debug, PartialEq)]
+pub struct CellReference {
+    pub sheet: String,
+    pub start: (u32, u32),
+    pub end: (u32, u32),
+}
+
+impl CellReference {
+    /// Parses an Excel (`Sheet1!$A$1:$D$20`) or OpenDocument (`$Sheet1.$A$1:.$D$20`) reference,
+    /// also to whole columns (`Sheet1!$A:$C`) or rows (`Sheet1!$1:$3`), `None` for anything
+    /// else (formulas, constants, several areas).
+    pub fn parse(formula: &str) -> Option<Self> {
+        let formula = formula.trim().trim_start_matches('=');
+        if has_area_separator(formula) {
+            return None;
+        }
+        let (sheet, start, end) = match formula.rsplit_once('!') {
+            Some((sheet, area)) => {
+                let (start, end) = area.split_once(':').unwrap_or((area, area));
+                (sheet, start, end)
+            }
+            None => {
+                let (start, end) = formula.split_once(':').unwrap_or((formula, formula));
+                let (sheet, start) = start.rsplit_once('.')?;
+                let end = end.rsplit_once('.').map_or(end, |(_, cell)| cell);
+                (sheet, start, end)
+            }
+        };
+        let (start, end) = match (parse_cell(start)?, parse_cell(end)?) {
+            ((Some(r0), Some(c0)), (Some(r1), Some(c1))) => ((r0, c0), (r1, c1)),
+            // a column or row alone is only a reference as an area, e.g. `$A:$A`
+            ((None, Some(c0)), (None, Some(c1))) if formula.contains(':') => {
+                ((0, c0), (MAX_ROW, c1))
+            }
+            ((Some(r0), None), (Some(r1), None)) if formula.contains(':') => {
+                ((r0, 0), (r1, MAX_COLUMN))
+            }
+            _ => return None,
+        };
+        Some(CellReference {
+            sheet: unquote_sheet(sheet)?,
+            start: (start.0.min(end.0), start.1.min(end.1)),
+            end: (start.0.max(end.0), start.1.max(end.1)),
+        })
+    }
+}
+
+/// Whether the formula lists several areas, separated by `,` (Excel) or whitespace
+/// (OpenDocument) outside of quoted sheet names.
+fn has_area_separator(formula: &str) -> bool {
+    let mut quoted = false;
+    formula.chars().any(|c| {
+        if c == '\'' {
+            quoted = !quoted;
+        }
+        !quoted && (c == ',' || c.is_whitespace())
+    })
+}
+
+fn unquote_sheet(sheet: &str) -> Option<String> {
+    let sheet = sheet.trim_start_matches('$');
+    let sheet = match sheet.strip_prefix('\'') {
+        Some(quoted) => quoted.strip_suffix('\'')?.replace("''", "'"),
+        None => sheet.to_string(),
+    };
+    (!sheet.is_empty()).then_some(sheet)
+}
+
+/// Parses an `A1` style cell, with or without `$`, into its row and column. The row is
+/// `None` for a column alone like `A`, the column for a row alone like `1`.
+fn parse_cell(cell: &str) -> Option<(Option<u32>, Option<u32>)> {
+    let cell = cell.replace('$', "");
+    let digits = cell
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(cell.len());
+    let (letters, digits) = cell.split_at(digits);
+    let column = match letters {
+        "" => None,
+        letters => Some(parse_column(letters)?),
+    };
+    let row = match digits {
+        "" => None,
+        digits => Some(digits.parse::<u32>().ok()?.checked_sub(1)?),
+    };
+    (row.is_some() || column.is_some()).then_some((row, column))
+}
+
+/// Parses column letters, e.g. `AB`, into a zero-based index.
+pub(crate) fn parse_column(letters: &str) -> Option<u32> {
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let column = letters.chars().try_fold(0u32, |acc, c| {
+        acc.checked_mul(26)?
+            .checked_add(c.to_ascii_uppercase() as u32 - 'A' as u32 + 1)
+    })?;
+    Some(column - 1)
+}
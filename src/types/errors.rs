@@ -5,13 +5,28 @@ use pyo3::{create_exception, PyErr};
 #[derive(Debug)]
 pub enum Error {
     Calamine(CalamineCrateError),
+    /// No reader could open the file, errors of every reader which was tried
+    FormatNotDetected(Vec<(&'static str, CalamineCrateError)>),
     TablesNotSupported,
     TablesNotLoaded,
     WorkbookClosed,
+    /// The password doesn't match the encryption verifier
+    InvalidPassword,
+    UnsupportedEncryption(String),
+    DefinedNameNotFound(String),
+    /// The defined name and its formula, which isn't a cell range
+    UnsupportedReference(String, String),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Calamine(CalamineCrateError::Io(error))
+    }
 }
 
 create_exception!(python_calamine, CalamineError, PyException);
 create_exception!(python_calamine, PasswordError, CalamineError);
+create_exception!(python_calamine, InvalidPasswordError, PasswordError);
 create_exception!(python_calamine, WorksheetNotFound, CalamineError);
 create_exception!(python_calamine, XmlError, CalamineError);
 create_exception!(python_calamine, ZipError, CalamineError);
@@ -19,6 +34,17 @@ create_exception!(python_calamine, WorkbookClosed, CalamineError);
 create_exception!(python_calamine, TablesNotSupported, CalamineError);
 create_exception!(python_calamine, TablesNotLoaded, CalamineError);
 create_exception!(python_calamine, TableNotFound, CalamineError);
+create_exception!(python_calamine, DefinedNameNotFound, CalamineError);
+
+fn is_password_error(error: &CalamineCrateError) -> bool {
+    matches!(
+        error,
+        CalamineCrateError::Ods(OdsError::Password)
+            | CalamineCrateError::Xls(XlsError::Password)
+            | CalamineCrateError::Xlsx(XlsxError::Password)
+            | CalamineCrateError::Xlsb(XlsbError::Password)
+    )
+}
 
 impl From<Error> for PyErr {
     fn from(val: Error) -> Self {
@@ -70,6 +96,28 @@ impl From<Error> for PyErr {
                 },
                 _ => CalamineError::new_err(calamine_error.to_string()),
             },
+            Error::FormatNotDetected(mut errors) => {
+                // encrypted files are rejected by the reader of their format
+                if let Some(index) = errors.iter().position(|(_, e)| is_password_error(e)) {
+                    return Error::Calamine(errors.swap_remove(index).1).into();
+                }
+                let tried = errors
+                    .iter()
+                    .map(|(format, error)| format!("{format} ({error})"))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                CalamineError::new_err(format!("Cannot detect file format, tried: {tried}"))
+            }
+            Error::InvalidPassword => InvalidPasswordError::new_err("Invalid password"),
+            Error::UnsupportedEncryption(encryption) => {
+                PasswordError::new_err(format!("Unsupported encryption: {encryption}"))
+            }
+            Error::DefinedNameNotFound(name) => {
+                DefinedNameNotFound::new_err(format!("Defined name '{name}' not found"))
+            }
+            Error::UnsupportedReference(name, formula) => CalamineError::new_err(format!(
+                "Defined name '{name}' is not a cell range: {formula}"
+            )),
             Error::WorkbookClosed => WorkbookClosed::new_err("".to_string()),
             Error::TablesNotLoaded => TablesNotLoaded::new_err("".to_string()),
             Error::TablesNotSupported => TablesNotSupported::new_err("".to_string()),
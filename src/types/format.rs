@@ -0,0 +1,447 @@
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+use cfb::CompoundFile;
+use pyo3::buffer::PyBuffer;
+use pyo3::prelude::*;
+use pyo3_file::PyFileLikeObject;
+use quick_xml::events::Event;
+use quick_xml::XmlVersion;
+use zip::ZipArchive;
+
+use crate::types::reader::{BufferBytes, PyFileReader};
+
+pub(crate) const CFB_SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+pub(crate) const ZIP_SIGNATURE: [u8; 4] = [0x50, 0x4B, 0x03, 0x04];
+const ODS_MIMETYPE: &str = "application/vnd.oasis.opendocument.spreadsheet";
+
+#[pyclass(eq, eq_int, from_py_object)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WorkbookFormatEnum {
+    /// Excel 97-2003 binary workbook (BIFF in a compound file)
+    Xls,
+    /// Office Open XML workbook
+    Xlsx,
+    /// Office Open XML macro-enabled workbook
+    Xlsm,
+    /// Excel binary workbook
+    Xlsb,
+    /// OpenDocument spreadsheet
+    Ods,
+    /// Password-protected Office Open XML workbook (xlsx, xlsm or xlsb), the real format is unknown
+    EncryptedOoxml,
+    /// Not a supported workbook
+    Unknown,
+}
+
+impl Display for WorkbookFormatEnum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WorkbookFormatEnum.{self:?}")
+    }
+}
+
+#[pyclass(from_py_object)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormatInfo {
+    #[pyo3(get)]
+    format: WorkbookFormatEnum,
+    #[pyo3(get)]
+    biff_version: Option<u8>,
+    #[pyo3(get)]
+    encrypted: bool,
+    #[pyo3(get)]
+    encryption: Option<String>,
+}
+
+#[pymethods]
+impl FormatInfo {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "FormatInfo(format={}, biff_version={}, encrypted={}, encryption={})",
+            self.format,
+            self.biff_version
+                .map_or("None".to_string(), |v| v.to_string()),
+            if self.encrypted { "True" } else { "False" },
+            self.encryption
+                .as_ref()
+                .map_or("None".to_string(), |v| format!("'{v}'")),
+        ))
+    }
+}
+
+impl FormatInfo {
+    fn new(format: WorkbookFormatEnum) -> Self {
+        FormatInfo {
+            format,
+            biff_version: None,
+            encrypted: false,
+            encryption: None,
+        }
+    }
+
+    fn encrypted(format: WorkbookFormatEnum, encryption: &str) -> Self {
+        FormatInfo {
+            format,
+            biff_version: None,
+            encrypted: true,
+            encryption: Some(encryption.to_string()),
+        }
+    }
+
+    pub fn format(&self) -> WorkbookFormatEnum {
+        self.format
+    }
+
+    pub fn biff_version(&self) -> Option<u8> {
+        self.biff_version
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypted
+    }
+
+    /// Detects the format of a path, bytes-like object or seekable file-like object.
+    ///
+    /// The position of a file-like object is restored afterwards, also on errors.
+    pub fn from_object(py: Python<'_>, path_or_filelike: Py<PyAny>) -> PyResult<Self> {
+        if let Ok(string_ref) = path_or_filelike.extract::<PathBuf>(py) {
+            return py.detach(|| {
+                let mut reader = BufReader::new(File::open(string_ref)?);
+                Ok(detect(&mut reader)?)
+            });
+        }
+
+        if let Ok(buffer) = PyBuffer::<u8>::get(path_or_filelike.bind(py)) {
+            return match BufferBytes::new(buffer) {
+                Ok(bytes) => py.detach(|| Ok(detect(&mut Cursor::new(bytes))?)),
+                Err(buffer) => {
+                    let buf = buffer.to_vec(py)?;
+                    py.detach(|| Ok(detect(&mut Cursor::new(buf))?))
+                }
+            };
+        }
+
+        let position = path_or_filelike.call_method0(py, "tell")?;
+        let filelike = path_or_filelike.clone_ref(py);
+        let info = py.detach(|| -> PyResult<Self> {
+            let file = PyFileLikeObject::with_requirements(filelike, true, false, true, false)?;
+            Ok(detect(&mut PyFileReader::new(file)?)?)
+        });
+        path_or_filelike.call_method1(py, "seek", (position,))?;
+        info
+    }
+}
+
+/// Detects the format by the signature, the compound file directory or the zip entries,
+/// without parsing the workbook itself.
+pub fn detect<RS: Read + Seek>(reader: &mut RS) -> io::Result<FormatInfo> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut signature = [0u8; 8];
+    let mut len = 0;
+    while len < signature.len() {
+        match reader.read(&mut signature[len..])? {
+            0 => break,
+            n => len += n,
+        }
+    }
+    reader.seek(SeekFrom::Start(0))?;
+
+    if signature == CFB_SIGNATURE {
+        Ok(detect_cfb(reader).unwrap_or(FormatInfo::new(WorkbookFormatEnum::Unknown)))
+    } else if signature.starts_with(&ZIP_SIGNATURE) {
+        Ok(detect_zip(reader).unwrap_or(FormatInfo::new(WorkbookFormatEnum::Unknown)))
+    } else {
+        Ok(FormatInfo::new(WorkbookFormatEnum::Unknown))
+    }
+}
+
+fn read_u16(data: &[u8]) -> u16 {
+    u16::from_le_bytes([data[0], data[1]])
+}
+
+fn detect_cfb<RS: Read + Seek>(reader: &mut RS) -> io::Result<FormatInfo> {
+    let mut cfb = CompoundFile::open(reader)?;
+
+    if cfb.is_stream("EncryptionInfo") && cfb.is_stream("EncryptedPackage") {
+        // [MS-OFFCRYPTO] 2.3.4.5 and 2.3.4.10, the version is the first field of EncryptionInfo
+        let mut version = [0u8; 4];
+        cfb.open_stream("EncryptionInfo")?
+            .read_exact(&mut version)?;
+        let encryption = match (read_u16(&version[..2]), read_u16(&version[2..])) {
+            (4, 4) => "agile",
+            (_, 2) => "standard",
+            (_, 3) => "extensible",
+            _ => "unknown",
+        };
+        return Ok(FormatInfo::encrypted(
+            WorkbookFormatEnum::EncryptedOoxml,
+            encryption,
+        ));
+    }
+
+    let Some(mut stream) = open_workbook_stream(&mut cfb)? else {
+        return Ok(FormatInfo::new(WorkbookFormatEnum::Unknown));
+    };
+    let mut info = FormatInfo::new(WorkbookFormatEnum::Xls);
+    info.biff_version = read_bof(&mut stream)?;
+    if info.biff_version.is_none() {
+        return Ok(info);
+    }
+
+    // FilePass [MS-XLS 2.4.117] must immediately follow the BOF of the globals substream
+    if let Some((0x002F, filepass)) = read_biff_record(&mut stream)? {
+        info.encrypted = true;
+        info.encryption = Some(
+            match filepass.as_slice() {
+                [0, 0, ..] => "xor",
+                [1, 0, 1, 0, ..] => "rc4",
+                [1, 0, ..] => "rc4-cryptoapi",
+                _ => "unknown",
+            }
+            .to_string(),
+        );
+    }
+
+    Ok(info)
+}
+
+fn open_workbook_stream<RS: Read + Seek>(
+    cfb: &mut CompoundFile<RS>,
+) -> io::Result<Option<cfb::Stream<RS>>> {
+    match ["Workbook", "Book"].into_iter().find(|n| cfb.is_stream(n)) {
+        Some(name) => cfb.open_stream(name).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Reads the next BIFF record, `None` at the end of the stream.
+fn read_biff_record<R: Read>(stream: &mut R) -> io::Result<Option<(u16, Vec<u8>)>> {
+    let mut header = [0u8; 4];
+    match stream.read_exact(&mut header) {
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        r => r?,
+    }
+    let mut data = vec![0u8; read_u16(&header[2..]) as usize];
+    stream.read_exact(&mut data)?;
+    Ok(Some((read_u16(&header), data)))
+}
+
+/// Reads the BOF record [MS-XLS 2.4.21] and returns the BIFF version, `None` when the
+/// stream does not start with a BOF record or the version is not recognised.
+fn read_bof<R: Read>(stream: &mut R) -> io::Result<Option<u8>> {
+    let Some((typ, data)) = read_biff_record(stream)? else {
+        return Ok(None);
+    };
+    // BIFF2 to BIFF4 have their own record types, BIFF5 and BIFF8 share 0x0809
+    Ok(match typ {
+        0x0009 => Some(2),
+        0x0209 => Some(3),
+        0x0409 => Some(4),
+        0x0809 if data.len() >= 2 => match read_u16(&data) {
+            0x0500 => Some(5),
+            0x0600 => Some(8),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+fn read_zip_string<RS: Read + Seek>(zip: &mut ZipArchive<RS>, name: &str) -> Option<String> {
+    let mut content = String::new();
+    zip.by_name(name).ok()?.read_to_string(&mut content).ok()?;
+    Some(content)
+}
+
+fn detect_zip<RS: Read + Seek>(reader: &mut RS) -> io::Result<FormatInfo> {
+    let mut zip = ZipArchive::new(reader).map_err(io::Error::other)?;
+
+    if zip.index_for_name("xl/workbook.bin").is_some() {
+        return Ok(FormatInfo::new(WorkbookFormatEnum::Xlsb));
+    }
+
+    if zip.index_for_name("xl/workbook.xml").is_some() {
+        let content_types = read_zip_string(&mut zip, "[Content_Types].xml").unwrap_or_default();
+        if content_types.contains("macroEnabled.main+xml") {
+            return Ok(FormatInfo::new(WorkbookFormatEnum::Xlsm));
+        }
+        return Ok(FormatInfo::new(WorkbookFormatEnum::Xlsx));
+    }
+
+    let mimetype = read_zip_string(&mut zip, "mimetype").unwrap_or_default();
+    if mimetype.trim_end().starts_with(ODS_MIMETYPE) {
+        let manifest = read_zip_string(&mut zip, "META-INF/manifest.xml").unwrap_or_default();
+        if manifest.contains("encryption-data") {
+            return Ok(FormatInfo::encrypted(WorkbookFormatEnum::Ods, "odf"));
+        }
+        return Ok(FormatInfo::new(WorkbookFormatEnum::Ods));
+    }
+
+    Ok(FormatInfo::new(WorkbookFormatEnum::Unknown))
+}
+
+/// Details of an opened workbook which calamine doesn't expose.
+#[derive(Clone, Debug, Default)]
+pub struct WorkbookInfo {
+    pub macros_enabled: bool,
+    pub biff_version: Option<u8>,
+    /// Name and sheet index of every defined name, `None` for workbook-level names
+    pub defined_name_scopes: Vec<(String, Option<usize>)>,
+}
+
+impl WorkbookInfo {
+    pub fn xls<RS: Read + Seek>(reader: RS) -> io::Result<Self> {
+        let mut cfb = CompoundFile::open(reader)?;
+        let macros_enabled = cfb.is_storage("_VBA_PROJECT_CUR");
+        let mut biff_version = None;
+        let mut defined_name_scopes = Vec::new();
+        if let Some(mut stream) = open_workbook_stream(&mut cfb)? {
+            biff_version = read_bof(&mut stream)?;
+            // Lbl [MS-XLS 2.4.150] records of the globals substream, itab is one-based
+            while let Some((typ, data)) = read_biff_record(&mut stream)? {
+                match typ {
+                    0x0018 if data.len() >= 14 => defined_name_scopes.push((
+                        xls_lbl_name(&data, biff_version),
+                        read_u16(&data[8..])
+                            .checked_sub(1)
+                            .map(|itab| itab as usize),
+                    )),
+                    // EOF
+                    0x000A => break,
+                    _ => {}
+                }
+            }
+        }
+        Ok(WorkbookInfo {
+            macros_enabled,
+            biff_version,
+            defined_name_scopes,
+        })
+    }
+
+    /// Reads an XLSX or XLSB workbook.
+    pub fn xlsx<RS: Read + Seek>(reader: RS) -> io::Result<Self> {
+        let mut zip = ZipArchive::new(reader).map_err(io::Error::other)?;
+        let content_types = read_zip_string(&mut zip, "[Content_Types].xml").unwrap_or_default();
+        let defined_name_scopes = if zip.index_for_name("xl/workbook.bin").is_some() {
+            let mut workbook = Vec::new();
+            zip.by_name("xl/workbook.bin")
+                .map_err(io::Error::other)?
+                .read_to_end(&mut workbook)?;
+            xlsb_defined_name_scopes(&workbook)
+        } else {
+            let workbook = read_zip_string(&mut zip, "xl/workbook.xml").unwrap_or_default();
+            xlsx_defined_name_scopes(&workbook)
+        };
+        Ok(WorkbookInfo {
+            macros_enabled: content_types.contains("macroEnabled.main+xml")
+                || zip.index_for_name("xl/vbaProject.bin").is_some(),
+            biff_version: None,
+            defined_name_scopes,
+        })
+    }
+
+    pub fn ods<RS: Read + Seek>(reader: RS) -> io::Result<Self> {
+        let zip = ZipArchive::new(reader).map_err(io::Error::other)?;
+        let macros_enabled = zip.file_names().any(|name| name.starts_with("Basic/"));
+        // calamine only reads the workbook-level named expressions
+        Ok(WorkbookInfo {
+            macros_enabled,
+            biff_version: None,
+            defined_name_scopes: Vec::new(),
+        })
+    }
+}
+
+/// Reads the name of a Lbl record the way calamine does, so that both compare equal.
+fn xls_lbl_name(data: &[u8], biff_version: Option<u8>) -> String {
+    let cch = data[3] as usize;
+    let name = &data[14..];
+    match biff_version {
+        // XLUnicodeStringNoCch, calamine reads cch bytes after the flags also for UTF-16
+        Some(8) if name.first().is_some_and(|flags| flags & 0x1 != 0) => {
+            let units: Vec<u16> = name[1..]
+                .chunks_exact(2)
+                .take(cch / 2)
+                .map(read_u16)
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        Some(8) => name.iter().skip(1).take(cch).map(|&b| b as char).collect(),
+        _ => name.iter().take(cch).map(|&b| b as char).collect(),
+    }
+}
+
+/// Reads the name and the `localSheetId` of the `definedName` elements.
+fn xlsx_defined_name_scopes(workbook: &str) -> Vec<(String, Option<usize>)> {
+    let mut reader = quick_xml::Reader::from_str(workbook);
+    let mut scopes = Vec::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"definedName" => {
+                let Some(name) = e
+                    .try_get_attribute("name")
+                    .ok()
+                    .flatten()
+                    .and_then(|a| {
+                        a.decoded_and_normalized_value(XmlVersion::Implicit1_0, reader.decoder())
+                            .ok()
+                    })
+                    .map(|v| v.into_owned())
+                else {
+                    continue;
+                };
+                let scope = e
+                    .try_get_attribute("localSheetId")
+                    .ok()
+                    .flatten()
+                    .and_then(|a| std::str::from_utf8(&a.value).ok()?.parse().ok());
+                scopes.push((name, scope));
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    scopes
+}
+
+/// Reads a variable-length field of a record header [MS-XLSB 2.1.4].
+fn read_xlsb_varint(data: &[u8], offset: &mut usize, max_len: usize) -> Option<usize> {
+    let mut value = 0;
+    for i in 0..max_len {
+        let byte = *data.get(*offset)?;
+        *offset += 1;
+        value |= ((byte & 0x7F) as usize) << (7 * i);
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    Some(value)
+}
+
+/// Reads the name and the itab of the BrtName [MS-XLSB 2.4.702] records.
+fn xlsb_defined_name_scopes(workbook: &[u8]) -> Vec<(String, Option<usize>)> {
+    let mut scopes = Vec::new();
+    let mut offset = 0;
+    while let (Some(typ), Some(len)) = (
+        read_xlsb_varint(workbook, &mut offset, 2),
+        read_xlsb_varint(workbook, &mut offset, 4),
+    ) {
+        let Some(data) = workbook.get(offset..offset + len) else {
+            break;
+        };
+        offset += len;
+        if typ == 0x0027 && data.len() >= 13 {
+            // XLWideString, the length is the number of UTF-16 units
+            let cch = u32::from_le_bytes([data[9], data[10], data[11], data[12]]) as usize;
+            let units: Vec<u16> = data[13..].chunks_exact(2).take(cch).map(read_u16).collect();
+            let scope = match u32::from_le_bytes([data[5], data[6], data[7], data[8]]) {
+                0xFFFF_FFFF => None,
+                itab => Some(itab as usize),
+            };
+            scopes.push((String::from_utf16_lossy(&units), scope));
+        }
+    }
+    scopes
+}
@@ -0,0 +1,483 @@
+//! The dataframe interchange protocol, see
+//! https://data-apis.org/dataframe-protocol/latest/API.html
+
+use std::sync::{Arc, OnceLock};
+
+use calamine::{Data, Range};
+use chrono::NaiveDate;
+use pyo3::exceptions::{PyKeyError, PyNotImplementedError, PyTypeError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::types::columns::{column_kinds, to_string, Column, Kind};
+use crate::CellValue;
+
+// DtypeKind
+const INT: u8 = 0;
+const UINT: u8 = 1;
+const FLOAT: u8 = 2;
+const BOOL: u8 = 20;
+const STRING: u8 = 21;
+const DATETIME: u8 = 22;
+
+// ColumnNullType, the validity bytes are 0 for nulls
+const USE_BYTEMASK: u8 = 4;
+
+// DlpackDeviceType
+const CPU: u8 = 1;
+
+type Dtype = (u8, u8, &'static str, &'static str);
+
+const INT64: Dtype = (INT, 64, "l", "=");
+const FLOAT64: Dtype = (FLOAT, 64, "g", "=");
+const BOOL8: Dtype = (BOOL, 8, "b", "=");
+
+/// Data of a buffer, typed to be aligned for the values it holds.
+enum Buffer {
+    U8(Vec<u8>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    F64(Vec<f64>),
+}
+
+impl Buffer {
+    fn as_ptr(&self) -> usize {
+        match self {
+            Buffer::U8(v) => v.as_ptr() as usize,
+            Buffer::I32(v) => v.as_ptr() as usize,
+            Buffer::I64(v) => v.as_ptr() as usize,
+            Buffer::F64(v) => v.as_ptr() as usize,
+        }
+    }
+
+    /// Size in bytes.
+    fn len(&self) -> usize {
+        match self {
+            Buffer::U8(v) => v.len(),
+            Buffer::I32(v) => size_of_val(v.as_slice()),
+            Buffer::I64(v) => size_of_val(v.as_slice()),
+            Buffer::F64(v) => size_of_val(v.as_slice()),
+        }
+    }
+}
+
+impl From<Vec<u8>> for Buffer {
+    fn from(values: Vec<u8>) -> Self {
+        Buffer::U8(values)
+    }
+}
+
+impl From<Vec<i32>> for Buffer {
+    fn from(values: Vec<i32>) -> Self {
+        Buffer::I32(values)
+    }
+}
+
+impl From<Vec<i64>> for Buffer {
+    fn from(values: Vec<i64>) -> Self {
+        Buffer::I64(values)
+    }
+}
+
+impl From<Vec<f64>> for Buffer {
+    fn from(values: Vec<f64>) -> Self {
+        Buffer::F64(values)
+    }
+}
+
+/// A contiguous block of memory of a column.
+#[pyclass]
+pub struct CalamineInterchangeBuffer {
+    data: Arc<Buffer>,
+}
+
+#[pymethods]
+impl CalamineInterchangeBuffer {
+    #[getter]
+    fn bufsize(&self) -> usize {
+        self.data.len()
+    }
+
+    #[getter]
+    fn ptr(&self) -> usize {
+        self.data.as_ptr()
+    }
+
+    fn __dlpack__(&self) -> PyResult<()> {
+        Err(PyNotImplementedError::new_err("DLPack isn't supported"))
+    }
+
+    fn __dlpack_device__(&self) -> (u8, Option<u8>) {
+        (CPU, None)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "CalamineInterchangeBuffer(bufsize={}, ptr={})",
+            self.bufsize(),
+            self.ptr()
+        )
+    }
+}
+
+/// Buffers of a column converted from the cells.
+struct Buffers {
+    dtype: Dtype,
+    data_dtype: Dtype,
+    data: Arc<Buffer>,
+    validity: Arc<Buffer>,
+    offsets: Option<Arc<Buffer>>,
+    null_count: usize,
+}
+
+fn primitive<T, U>(
+    dtype: Dtype,
+    data_dtype: Dtype,
+    values: Vec<Option<T>>,
+    to_value: impl Fn(Option<T>) -> U,
+) -> Buffers
+where
+    Buffer: From<Vec<U>>,
+{
+    let validity: Vec<u8> = values.iter().map(|v| u8::from(v.is_some())).collect();
+    let data: Vec<U> = values.into_iter().map(to_value).collect();
+    Buffers {
+        dtype,
+        data_dtype,
+        null_count: validity.iter().filter(|valid| **valid == 0).count(),
+        data: Arc::new(data.into()),
+        validity: Arc::new(validity.into()),
+        offsets: None,
+    }
+}
+
+fn strings(values: Vec<Option<String>>) -> Buffers {
+    let mut data = Vec::new();
+    let mut offsets = vec![0i64];
+    for value in &values {
+        data.extend_from_slice(value.as_deref().unwrap_or_default().as_bytes());
+        offsets.push(data.len() as i64);
+    }
+    let validity: Vec<u8> = values.iter().map(|v| u8::from(v.is_some())).collect();
+    Buffers {
+        dtype: (STRING, 8, "U", "="),
+        data_dtype: (UINT, 8, "C", "="),
+        null_count: validity.iter().filter(|valid| **valid == 0).count(),
+        data: Arc::new(data.into()),
+        validity: Arc::new(validity.into()),
+        offsets: Some(Arc::new(offsets.into())),
+    }
+}
+
+/// Converts a column to buffers, times and durations are strings as they
+/// aren't supported by the consumers.
+fn column_buffers(column: Column) -> Buffers {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    match column {
+        Column::Null(len) => primitive(FLOAT64, FLOAT64, vec![None::<f64>; len], |v| {
+            v.unwrap_or(f64::NAN)
+        }),
+        Column::Int(values) => primitive(INT64, INT64, values, Option::unwrap_or_default),
+        Column::Float(values) => primitive(FLOAT64, FLOAT64, values, |v| v.unwrap_or(f64::NAN)),
+        Column::Bool(values) => {
+            primitive(BOOL8, BOOL8, values, |v| u8::from(v.unwrap_or_default()))
+        }
+        // the data of dates and datetimes are days and microseconds since the epoch
+        Column::Date(values) => primitive(
+            (DATETIME, 32, "tdD", "="),
+            (INT, 32, "i", "="),
+            values,
+            |v| v.map_or(0, |v| v.signed_duration_since(epoch).num_days() as i32),
+        ),
+        Column::DateTime(values) => primitive((DATETIME, 64, "tsu:", "="), INT64, values, |v| {
+            v.map_or(0, |v| v.and_utc().timestamp_micros())
+        }),
+        Column::String(values) => strings(values),
+        Column::Time(values) => strings(
+            values
+                .into_iter()
+                .map(|v| v.and_then(|v| to_string(CellValue::Time(v))))
+                .collect(),
+        ),
+        Column::Timedelta(values) => strings(
+            values
+                .into_iter()
+                .map(|v| v.and_then(|v| to_string(CellValue::Timedelta(v))))
+                .collect(),
+        ),
+        Column::Mixed(values) => strings(values.into_iter().map(to_string).collect()),
+    }
+}
+
+/// Types of the columns of a range, inferred from all rows after `skip` on first use and
+/// shared by the frames and columns of the range, so the chunks have the same types.
+struct Kinds {
+    range: Arc<Range<Data>>,
+    skip: usize,
+    kinds: Vec<OnceLock<Kind>>,
+}
+
+impl Kinds {
+    fn new(range: Arc<Range<Data>>, skip: usize) -> Self {
+        Kinds {
+            kinds: (0..range.width()).map(|_| OnceLock::new()).collect(),
+            range,
+            skip,
+        }
+    }
+
+    fn get(&self, py: Python<'_>, position: usize) -> Kind {
+        if let Some(kind) = self.kinds[position].get() {
+            return *kind;
+        }
+        let kind = py.detach(|| column_kinds(&self.range, self.skip, None, &[position])[0]);
+        *self.kinds[position].get_or_init(|| kind)
+    }
+}
+
+/// A column of the rows `start..end`, converted on first use.
+#[pyclass(frozen)]
+pub struct CalamineInterchangeColumn {
+    kinds: Arc<Kinds>,
+    position: usize,
+    start: usize,
+    end: usize,
+    buffers: OnceLock<Buffers>,
+}
+
+impl CalamineInterchangeColumn {
+    fn new(kinds: Arc<Kinds>, position: usize, start: usize, end: usize) -> Self {
+        CalamineInterchangeColumn {
+            kinds,
+            position,
+            start,
+            end,
+            buffers: OnceLock::new(),
+        }
+    }
+
+    fn buffers(&self, py: Python<'_>) -> &Buffers {
+        if let Some(buffers) = self.buffers.get() {
+            return buffers;
+        }
+        let kind = self.kinds.get(py, self.position);
+        let buffers = py.detach(|| {
+            let cells = self
+                .kinds
+                .range
+                .rows()
+                .skip(self.start)
+                .take(self.end - self.start)
+                .map(|row| CellValue::from(&row[self.position]))
+                .collect();
+            column_buffers(Column::with_kind(kind, cells))
+        });
+        self.buffers.get_or_init(|| buffers)
+    }
+}
+
+#[pymethods]
+impl CalamineInterchangeColumn {
+    fn size(&self) -> usize {
+        self.end - self.start
+    }
+
+    #[getter]
+    fn offset(&self) -> usize {
+        0
+    }
+
+    #[getter]
+    fn dtype(&self, py: Python<'_>) -> Dtype {
+        self.buffers(py).dtype
+    }
+
+    #[getter]
+    fn describe_categorical(&self) -> PyResult<()> {
+        Err(PyTypeError::new_err("Column isn't categorical"))
+    }
+
+    #[getter]
+    fn describe_null(&self) -> (u8, u8) {
+        (USE_BYTEMASK, 0)
+    }
+
+    #[getter]
+    fn null_count(&self, py: Python<'_>) -> usize {
+        self.buffers(py).null_count
+    }
+
+    #[getter]
+    fn metadata<'py>(&self, py: Python<'py>) -> Bound<'py, PyDict> {
+        PyDict::new(py)
+    }
+
+    fn num_chunks(&self) -> usize {
+        1
+    }
+
+    #[pyo3(signature = (n_chunks=None))]
+    fn get_chunks(&self, n_chunks: Option<usize>) -> Vec<CalamineInterchangeColumn> {
+        chunks(self.start, self.end, n_chunks)
+            .map(|(start, end)| {
+                CalamineInterchangeColumn::new(Arc::clone(&self.kinds), self.position, start, end)
+            })
+            .collect()
+    }
+
+    fn get_buffers<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let buffers = self.buffers(py);
+        let buffer = |data: &Arc<Buffer>| CalamineInterchangeBuffer {
+            data: Arc::clone(data),
+        };
+        let result = PyDict::new(py);
+        result.set_item("data", (buffer(&buffers.data), buffers.data_dtype))?;
+        result.set_item("validity", (buffer(&buffers.validity), BOOL8))?;
+        match &buffers.offsets {
+            Some(offsets) => result.set_item("offsets", (buffer(offsets), INT64))?,
+            None => result.set_item("offsets", py.None())?,
+        }
+        Ok(result)
+    }
+}
+
+/// Splits the rows `start..end` in `n_chunks` chunks of about the same size.
+fn chunks(
+    start: usize,
+    end: usize,
+    n_chunks: Option<usize>,
+) -> impl Iterator<Item = (usize, usize)> {
+    let n_chunks = n_chunks.unwrap_or(1).max(1);
+    let size = (end - start).div_ceil(n_chunks).max(1);
+    (0..n_chunks).map(move |i| {
+        let chunk_start = (start + i * size).min(end);
+        (chunk_start, (chunk_start + size).min(end))
+    })
+}
+
+/// The rows `start..end` of some columns of a range, named by `names`.
+#[pyclass(frozen)]
+pub struct CalamineInterchangeFrame {
+    kinds: Arc<Kinds>,
+    names: Vec<String>,
+    positions: Vec<usize>,
+    start: usize,
+    end: usize,
+}
+
+impl CalamineInterchangeFrame {
+    /// A frame of the rows after `skip`.
+    pub fn new(range: Arc<Range<Data>>, names: Vec<String>, skip: usize) -> Self {
+        let end = range.height();
+        let skip = skip.min(end);
+        CalamineInterchangeFrame {
+            kinds: Arc::new(Kinds::new(range, skip)),
+            positions: (0..names.len()).collect(),
+            start: skip,
+            end,
+            names,
+        }
+    }
+
+    fn select(&self, indices: Vec<usize>) -> PyResult<Self> {
+        let positions = indices
+            .iter()
+            .map(|&i| {
+                self.positions
+                    .get(i)
+                    .copied()
+                    .ok_or_else(|| PyKeyError::new_err(i))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(CalamineInterchangeFrame {
+            kinds: Arc::clone(&self.kinds),
+            names: indices.iter().map(|&i| self.names[i].clone()).collect(),
+            positions,
+            start: self.start,
+            end: self.end,
+        })
+    }
+
+    fn index(&self, name: &str) -> PyResult<usize> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .ok_or_else(|| PyKeyError::new_err(name.to_string()))
+    }
+}
+
+#[pymethods]
+impl CalamineInterchangeFrame {
+    #[pyo3(signature = (nan_as_null=false, allow_copy=true))]
+    fn __dataframe__(slf: Py<Self>, nan_as_null: bool, allow_copy: bool) -> Py<Self> {
+        let _ = (nan_as_null, allow_copy);
+        slf
+    }
+
+    #[getter]
+    fn metadata<'py>(&self, py: Python<'py>) -> Bound<'py, PyDict> {
+        PyDict::new(py)
+    }
+
+    fn num_columns(&self) -> usize {
+        self.names.len()
+    }
+
+    fn num_rows(&self) -> usize {
+        self.end - self.start
+    }
+
+    fn num_chunks(&self) -> usize {
+        1
+    }
+
+    fn column_names(&self) -> Vec<String> {
+        self.names.clone()
+    }
+
+    fn get_column(&self, i: usize) -> PyResult<CalamineInterchangeColumn> {
+        let position = *self
+            .positions
+            .get(i)
+            .ok_or_else(|| PyKeyError::new_err(i))?;
+        Ok(CalamineInterchangeColumn::new(
+            Arc::clone(&self.kinds),
+            position,
+            self.start,
+            self.end,
+        ))
+    }
+
+    fn get_column_by_name(&self, name: &str) -> PyResult<CalamineInterchangeColumn> {
+        self.get_column(self.index(name)?)
+    }
+
+    fn get_columns(&self) -> PyResult<Vec<CalamineInterchangeColumn>> {
+        (0..self.names.len()).map(|i| self.get_column(i)).collect()
+    }
+
+    fn select_columns(&self, indices: Vec<usize>) -> PyResult<CalamineInterchangeFrame> {
+        self.select(indices)
+    }
+
+    fn select_columns_by_name(&self, names: Vec<String>) -> PyResult<CalamineInterchangeFrame> {
+        let indices = names
+            .iter()
+            .map(|name| self.index(name))
+            .collect::<PyResult<_>>()?;
+        self.select(indices)
+    }
+
+    #[pyo3(signature = (n_chunks=None))]
+    fn get_chunks(&self, n_chunks: Option<usize>) -> Vec<CalamineInterchangeFrame> {
+        chunks(self.start, self.end, n_chunks)
+            .map(|(start, end)| CalamineInterchangeFrame {
+                kinds: Arc::clone(&self.kinds),
+                names: self.names.clone(),
+                positions: self.positions.clone(),
+                start,
+                end,
+            })
+            .collect()
+    }
+}
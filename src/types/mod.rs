@@ -1,13 +1,34 @@
+mod arrow;
+mod batches;
 mod cell;
+mod columns;
+mod crypto;
+mod defined_name;
 mod errors;
+mod format;
+mod interchange;
+mod numpy;
+mod pandas;
+mod picture;
+mod properties;
+mod reader;
+mod records;
+mod rows;
 mod sheet;
 mod table;
+mod usecols;
+mod vba;
 mod workbook;
 pub use cell::CellValue;
+pub use defined_name::DefinedName;
 pub use errors::{
-    CalamineError, Error, PasswordError, TableNotFound, TablesNotLoaded, TablesNotSupported,
-    WorkbookClosed, WorksheetNotFound, XmlError, ZipError,
+    CalamineError, DefinedNameNotFound, Error, InvalidPasswordError, PasswordError, TableNotFound,
+    TablesNotLoaded, TablesNotSupported, WorkbookClosed, WorksheetNotFound, XmlError, ZipError,
 };
+pub use format::{FormatInfo, WorkbookFormatEnum};
+pub use picture::Picture;
+pub use properties::DocumentProperties;
 pub use sheet::{CalamineSheet, SheetMetadata, SheetTypeEnum, SheetVisibleEnum};
 pub use table::CalamineTable;
+pub use vba::{VbaModule, VbaModuleTypeEnum, VbaProject, VbaReference};
 pub use workbook::CalamineWorkbook;
@@ -0,0 +1,132 @@
+//! NumPy arrays of sheet columns, the values are written to the array buffers in Rust.
+//! NumPy is an optional dependency, it is imported on use.
+
+use chrono::NaiveDate;
+use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyList, PySlice};
+
+use crate::types::columns::Column;
+use crate::CellValue;
+
+/// The value of NaT for `datetime64` and `timedelta64`.
+const NAT: i64 = i64::MIN;
+
+fn from_buffer<'py>(
+    numpy: &Bound<'py, PyModule>,
+    bytes: Vec<u8>,
+    dtype: &str,
+) -> PyResult<Bound<'py, PyAny>> {
+    let buffer = PyByteArray::new(numpy.py(), &bytes);
+    numpy.call_method1("frombuffer", (buffer, dtype))
+}
+
+/// Masks the empty cells of an array for the types without a missing value.
+fn masked<'py>(
+    numpy: &Bound<'py, PyModule>,
+    array: Bound<'py, PyAny>,
+    valid: impl Iterator<Item = bool>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let mask: Vec<u8> = valid.map(|valid| u8::from(!valid)).collect();
+    if !mask.contains(&1) {
+        return Ok(array);
+    }
+    let mask = from_buffer(numpy, mask, "bool")?;
+    numpy
+        .getattr("ma")?
+        .call_method1("masked_array", (array, mask))
+}
+
+fn int64_bytes(values: impl Iterator<Item = i64>) -> Vec<u8> {
+    values.flat_map(i64::to_ne_bytes).collect()
+}
+
+/// Converts a column to an array: float64, int64 and bool for numbers, `datetime64[D]`,
+/// `datetime64[us]` and `timedelta64[us]` for dates and durations, object for the others.
+/// Empty cells are NaN and NaT, int64 and bool arrays with empty cells are masked.
+pub fn column_to_ndarray(py: Python<'_>, column: Column) -> PyResult<Bound<'_, PyAny>> {
+    let numpy = py.import("numpy")?;
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    match column {
+        Column::Null(len) => from_buffer(
+            &numpy,
+            std::iter::repeat_n(f64::NAN, len)
+                .flat_map(f64::to_ne_bytes)
+                .collect(),
+            "float64",
+        ),
+        Column::Float(values) => from_buffer(
+            &numpy,
+            values
+                .into_iter()
+                .flat_map(|v| v.unwrap_or(f64::NAN).to_ne_bytes())
+                .collect(),
+            "float64",
+        ),
+        Column::Int(values) => {
+            let array = from_buffer(
+                &numpy,
+                int64_bytes(values.iter().map(|v| v.unwrap_or_default())),
+                "int64",
+            )?;
+            masked(&numpy, array, values.iter().map(Option::is_some))
+        }
+        Column::Bool(values) => {
+            let array = from_buffer(
+                &numpy,
+                values
+                    .iter()
+                    .map(|v| u8::from(v.unwrap_or_default()))
+                    .collect(),
+                "bool",
+            )?;
+            masked(&numpy, array, values.iter().map(Option::is_some))
+        }
+        Column::Date(values) => from_buffer(
+            &numpy,
+            int64_bytes(
+                values
+                    .into_iter()
+                    .map(|v| v.map_or(NAT, |v| v.signed_duration_since(epoch).num_days())),
+            ),
+            "datetime64[D]",
+        ),
+        Column::DateTime(values) => from_buffer(
+            &numpy,
+            int64_bytes(
+                values
+                    .into_iter()
+                    .map(|v| v.map_or(NAT, |v| v.and_utc().timestamp_micros())),
+            ),
+            "datetime64[us]",
+        ),
+        Column::Timedelta(values) => from_buffer(
+            &numpy,
+            int64_bytes(
+                values
+                    .into_iter()
+                    .map(|v| v.and_then(|v| v.num_microseconds()).unwrap_or(NAT)),
+            ),
+            "timedelta64[us]",
+        ),
+        // NumPy has no type for a time of day
+        Column::Time(values) => object_array(&numpy, PyList::new(py, values)?),
+        Column::String(values) => object_array(&numpy, PyList::new(py, values)?),
+        Column::Mixed(values) => {
+            let values = values.into_iter().map(|value| match value {
+                CellValue::Empty => None,
+                value => Some(value),
+            });
+            object_array(&numpy, PyList::new(py, values)?)
+        }
+    }
+}
+
+fn object_array<'py>(
+    numpy: &Bound<'py, PyModule>,
+    values: Bound<'py, PyList>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let array = numpy.call_method1("empty", (values.len(), "object"))?;
+    // assigned by a slice, `numpy.array` would make a 2D array from a list of equal sequences
+    array.set_item(PySlice::full(numpy.py()), values)?;
+    Ok(array)
+}
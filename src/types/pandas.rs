@@ -0,0 +1,62 @@
+//! pandas DataFrame of sheet columns, built from the NumPy arrays of `numpy.rs`.
+//! pandas is an optional dependency, it is imported on use.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::types::columns::Column;
+use crate::types::numpy::column_to_ndarray;
+use crate::CellValue;
+
+/// Keeps dates and datetimes as Python objects.
+fn without_dates(column: Column) -> Column {
+    match column {
+        Column::Date(values) => Column::Mixed(
+            values
+                .into_iter()
+                .map(|v| v.map_or(CellValue::Empty, CellValue::Date))
+                .collect(),
+        ),
+        Column::DateTime(values) => Column::Mixed(
+            values
+                .into_iter()
+                .map(|v| v.map_or(CellValue::Empty, CellValue::DateTime))
+                .collect(),
+        ),
+        column => column,
+    }
+}
+
+/// Builds a DataFrame from the columns, `dtype` is applied with `DataFrame.astype` and
+/// `index_col` is a column position or a label for `DataFrame.set_index`.
+pub fn to_dataframe<'py>(
+    py: Python<'py>,
+    names: Vec<Bound<'py, PyAny>>,
+    columns: Vec<Column>,
+    index_col: Option<Bound<'py, PyAny>>,
+    dtype: Option<Bound<'py, PyAny>>,
+    parse_dates: bool,
+) -> PyResult<Bound<'py, PyAny>> {
+    let pandas = py.import("pandas")?;
+    let data = PyDict::new(py);
+    for (name, column) in names.into_iter().zip(columns) {
+        let column = if parse_dates {
+            column
+        } else {
+            without_dates(column)
+        };
+        data.set_item(name, column_to_ndarray(py, column)?)?;
+    }
+    let mut frame = pandas.call_method1("DataFrame", (data,))?;
+    if let Some(dtype) = dtype {
+        frame = frame.call_method1("astype", (dtype,))?;
+    }
+    if let Some(index_col) = index_col {
+        let key = match index_col.extract::<usize>() {
+            Ok(position) => frame.getattr("columns")?.get_item(position)?,
+            Err(_) => index_col,
+        };
+        frame = frame.call_method1("set_index", (key,))?;
+    }
+    Ok(frame)
+}
@@ -0,0 +1,65 @@
+use calamine::Picture as CalaminePicture;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+#[pyclass(from_py_object)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Picture {
+    #[pyo3(get)]
+    extension: String,
+    data: Vec<u8>,
+    #[pyo3(get)]
+    sheet: Option<String>,
+    #[pyo3(get)]
+    anchor: Option<(u32, u32)>,
+    #[pyo3(get)]
+    name: Option<String>,
+}
+
+#[pymethods]
+impl Picture {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "Picture(extension='{}', size={}, sheet={}, anchor={})",
+            self.extension,
+            self.data.len(),
+            self.sheet
+                .as_ref()
+                .map_or("None".to_string(), |v| format!("'{v}'")),
+            self.anchor
+                .map_or("None".to_string(), |(row, col)| format!("({row}, {col})")),
+        ))
+    }
+
+    #[getter]
+    fn data<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.data)
+    }
+}
+
+impl Picture {
+    /// A picture without position, for formats where calamine only reads the media.
+    pub fn new(extension: String, data: Vec<u8>) -> Self {
+        Picture {
+            extension,
+            data,
+            sheet: None,
+            anchor: None,
+            name: None,
+        }
+    }
+}
+
+impl From<CalaminePicture> for Picture {
+    fn from(picture: CalaminePicture) -> Self {
+        // calamine uses an empty sheet name for media that isn't anchored in a drawing
+        let anchored = !picture.sheet_name.is_empty();
+        Picture {
+            extension: picture.extension,
+            data: picture.data,
+            sheet: anchored.then_some(picture.sheet_name),
+            anchor: anchored.then_some((picture.row, picture.col)),
+            name: (!picture.name.is_empty()).then_some(picture.name),
+        }
+    }
+}
@@ -0,0 +1,515 @@
+use std::collections::HashMap;
+use std::io::{self, Read, Seek};
+
+use cfb::CompoundFile;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta};
+use pyo3::prelude::*;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesRef, BytesStart, Event};
+use quick_xml::XmlVersion;
+use zip::ZipArchive;
+
+use crate::CellValue;
+
+#[pyclass(from_py_object)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DocumentProperties {
+    #[pyo3(get)]
+    title: Option<String>,
+    #[pyo3(get)]
+    subject: Option<String>,
+    #[pyo3(get)]
+    creator: Option<String>,
+    #[pyo3(get)]
+    keywords: Option<String>,
+    #[pyo3(get)]
+    last_modified_by: Option<String>,
+    #[pyo3(get)]
+    created: Option<NaiveDateTime>,
+    #[pyo3(get)]
+    modified: Option<NaiveDateTime>,
+    #[pyo3(get)]
+    company: Option<String>,
+    #[pyo3(get)]
+    application: Option<String>,
+    #[pyo3(get)]
+    app_version: Option<String>,
+}
+
+#[pymethods]
+impl DocumentProperties {
+    fn __repr__(&self) -> PyResult<String> {
+        let quoted =
+            |v: &Option<String>| v.as_ref().map_or("None".to_string(), |v| format!("'{v}'"));
+        Ok(format!(
+            "DocumentProperties(title={}, creator={}, modified={})",
+            quoted(&self.title),
+            quoted(&self.creator),
+            self.modified
+                .map_or("None".to_string(), |v| format!("'{v}'")),
+        ))
+    }
+}
+
+impl DocumentProperties {
+    /// Reads the SummaryInformation and DocumentSummaryInformation streams [MS-OSHARED 2.3.3.2].
+    pub fn xls<RS: Read + Seek>(reader: RS) -> io::Result<Self> {
+        let mut cfb = CompoundFile::open(reader)?;
+        let summary = read_property_stream(&mut cfb, "\u{5}SummaryInformation")?;
+        let document = read_property_stream(&mut cfb, "\u{5}DocumentSummaryInformation")?;
+        let summary = summary.first();
+        let document = document.first();
+        let string = |section: Option<&PropertySection>, id| match section?.get(id)? {
+            CellValue::String(v) => non_empty(v.clone()),
+            _ => None,
+        };
+        let datetime = |id| match summary?.get(id)? {
+            CellValue::DateTime(v) => Some(*v),
+            _ => None,
+        };
+        let app_version = match document.and_then(|section| section.get(0x17)) {
+            // PIDDSI_VERSION, the major version in the high-order word
+            Some(CellValue::Int(v)) => Some(format!("{}.{:04}", v >> 16, v & 0xFFFF)),
+            _ => None,
+        };
+        Ok(DocumentProperties {
+            title: string(summary, 0x02),
+            subject: string(summary, 0x03),
+            creator: string(summary, 0x04),
+            keywords: string(summary, 0x05),
+            last_modified_by: string(summary, 0x08),
+            created: datetime(0x0C),
+            modified: datetime(0x0D),
+            company: string(document, 0x0F),
+            application: string(summary, 0x12),
+            app_version,
+        })
+    }
+
+    /// Reads `docProps/core.xml` and `docProps/app.xml` of an XLSX or XLSB workbook.
+    pub fn xlsx<RS: Read + Seek>(reader: RS) -> io::Result<Self> {
+        let mut zip = ZipArchive::new(reader).map_err(io::Error::other)?;
+        let core = read_xml_text(&read_zip_string(&mut zip, "docProps/core.xml"));
+        let app = read_xml_text(&read_zip_string(&mut zip, "docProps/app.xml"));
+        let core_string = |name| core.get(name).cloned().and_then(non_empty);
+        let app_string = |name| app.get(name).cloned().and_then(non_empty);
+        Ok(DocumentProperties {
+            title: core_string("title"),
+            subject: core_string("subject"),
+            creator: core_string("creator"),
+            keywords: core_string("keywords"),
+            last_modified_by: core_string("lastModifiedBy"),
+            created: core.get("created").and_then(|v| parse_datetime(v)),
+            modified: core.get("modified").and_then(|v| parse_datetime(v)),
+            company: app_string("Company"),
+            application: app_string("Application"),
+            app_version: app_string("AppVersion"),
+        })
+    }
+
+    /// Reads `meta.xml` of an ODS document [ODF 1.2 part 1, 4].
+    pub fn ods<RS: Read + Seek>(reader: RS) -> io::Result<Self> {
+        let mut zip = ZipArchive::new(reader).map_err(io::Error::other)?;
+        let meta = read_xml_text(&read_zip_string(&mut zip, "meta.xml"));
+        let string = |name| meta.get(name).cloned().and_then(non_empty);
+        // <application>/<version>, followed by the platform and build separated by '$' or a space
+        let generator = meta.get("generator").and_then(|generator| {
+            let generator = generator.split(['$', ' ']).next()?;
+            non_empty(generator.to_string())
+        });
+        let (application, app_version) = match generator.as_deref().map(|g| g.split_once('/')) {
+            Some(Some((application, version))) => (
+                non_empty(application.to_string()),
+                non_empty(version.to_string()),
+            ),
+            _ => (generator, None),
+        };
+        Ok(DocumentProperties {
+            title: string("title"),
+            subject: string("subject"),
+            // dc:creator is the author of the last modification
+            creator: string("initial-creator"),
+            keywords: string("keyword"),
+            last_modified_by: string("creator"),
+            created: meta.get("creation-date").and_then(|v| parse_datetime(v)),
+            modified: meta.get("date").and_then(|v| parse_datetime(v)),
+            company: None,
+            application,
+            app_version,
+        })
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
+fn read_zip_string<RS: Read + Seek>(zip: &mut ZipArchive<RS>, name: &str) -> String {
+    let mut content = String::new();
+    if let Ok(mut file) = zip.by_name(name) {
+        file.read_to_string(&mut content).unwrap_or_default();
+    }
+    content
+}
+
+/// Reads the text of the leaf elements of an XML part by local name,
+/// repeated elements (e.g. `meta:keyword`) are joined with a comma.
+fn read_xml_text(xml: &str) -> HashMap<String, String> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut values: HashMap<String, String> = HashMap::new();
+    let mut text = String::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(_)) => text.clear(),
+            Ok(Event::Text(e)) => text.push_str(&e.xml10_content().unwrap_or_default()),
+            Ok(Event::GeneralRef(e)) => push_reference(&mut text, &e),
+            Ok(Event::End(e)) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                let value = text.trim().to_string();
+                text.clear();
+                let existing = values.entry(name).or_default();
+                if !existing.is_empty() && !value.is_empty() {
+                    existing.push_str(", ");
+                }
+                existing.push_str(&value);
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    values
+}
+
+/// Reads the custom properties of an XLS workbook, the user-defined section of the
+/// DocumentSummaryInformation stream [MS-OSHARED 2.3.3.2.2].
+pub fn custom_properties_xls<RS: Read + Seek>(reader: RS) -> io::Result<Vec<(String, CellValue)>> {
+    let mut cfb = CompoundFile::open(reader)?;
+    let sections = read_property_stream(&mut cfb, "\u{5}DocumentSummaryInformation")?;
+    let Some(PropertySection { properties, names }) = sections.into_iter().nth(1) else {
+        return Ok(Vec::new());
+    };
+    Ok(properties
+        .into_iter()
+        .filter_map(|(id, value)| Some((names.get(&id)?.clone(), value)))
+        .collect())
+}
+
+/// Reads `docProps/custom.xml` of an XLSX or XLSB workbook.
+pub fn custom_properties_xlsx<RS: Read + Seek>(reader: RS) -> io::Result<Vec<(String, CellValue)>> {
+    let mut zip = ZipArchive::new(reader).map_err(io::Error::other)?;
+    Ok(read_custom_xml(&read_zip_string(
+        &mut zip,
+        "docProps/custom.xml",
+    )))
+}
+
+/// Reads the `meta:user-defined` elements of `meta.xml` of an ODS document.
+pub fn custom_properties_ods<RS: Read + Seek>(reader: RS) -> io::Result<Vec<(String, CellValue)>> {
+    let mut zip = ZipArchive::new(reader).map_err(io::Error::other)?;
+    Ok(read_custom_xml(&read_zip_string(&mut zip, "meta.xml")))
+}
+
+/// Reads the `property` elements of `docProps/custom.xml`, typed by their `vt:` child,
+/// or the `meta:user-defined` elements of `meta.xml`, typed by `meta:value-type`.
+fn read_custom_xml(xml: &str) -> Vec<(String, CellValue)> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut properties = Vec::new();
+    // name and type of the current property
+    let mut current: Option<(String, String)> = None;
+    let mut text = String::new();
+    loop {
+        let (e, empty) = match reader.read_event() {
+            Ok(Event::Start(e)) => (e, false),
+            Ok(Event::Empty(e)) => (e, true),
+            Ok(Event::Text(e)) => {
+                text.push_str(&e.xml10_content().unwrap_or_default());
+                continue;
+            }
+            Ok(Event::GeneralRef(e)) => {
+                push_reference(&mut text, &e);
+                continue;
+            }
+            Ok(Event::End(e)) => {
+                if matches!(e.local_name().as_ref(), b"property" | b"user-defined") {
+                    if let Some((name, typ)) = current.take() {
+                        properties.push((name, typed_value(&typ, text.trim())));
+                    }
+                }
+                continue;
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => continue,
+        };
+        text.clear();
+        match e.local_name().as_ref() {
+            b"property" | b"user-defined" => {
+                let attributes = attributes(&e, &reader);
+                let typ = attributes
+                    .get("value-type")
+                    .map_or("string", |v| v.as_str());
+                current = attributes
+                    .get("name")
+                    .map(|name| (name.clone(), typ.to_string()));
+                if empty {
+                    if let Some((name, typ)) = current.take() {
+                        properties.push((name, typed_value(&typ, "")));
+                    }
+                }
+            }
+            typ => {
+                if let Some((_, current)) = current.as_mut() {
+                    *current = String::from_utf8_lossy(typ).into_owned();
+                }
+            }
+        }
+    }
+    properties
+}
+
+fn attributes(e: &BytesStart, reader: &quick_xml::Reader<&[u8]>) -> HashMap<String, String> {
+    e.attributes()
+        .filter_map(|a| {
+            let a = a.ok()?;
+            let value = a
+                .decoded_and_normalized_value(XmlVersion::Implicit1_0, reader.decoder())
+                .ok()?;
+            Some((
+                String::from_utf8_lossy(a.key.local_name().as_ref()).into_owned(),
+                value.into_owned(),
+            ))
+        })
+        .collect()
+}
+
+/// Converts the text of a `vt:` element [ECMA-376 part 1, 22.4] or of an ODF value type,
+/// values that don't parse are kept as strings.
+fn typed_value(typ: &str, text: &str) -> CellValue {
+    match typ {
+        "i1" | "i2" | "i4" | "i8" | "int" | "ui1" | "ui2" | "ui4" | "ui8" | "uint" => {
+            text.parse().ok().map(CellValue::Int)
+        }
+        "r4" | "r8" | "decimal" | "float" | "percentage" | "currency" => {
+            text.parse().ok().map(CellValue::Float)
+        }
+        "bool" | "boolean" => Some(CellValue::Bool(text == "true" || text == "1")),
+        "filetime" | "date" => parse_datetime(text).map(CellValue::DateTime).or_else(|| {
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()
+                .map(CellValue::Date)
+        }),
+        _ => None,
+    }
+    .unwrap_or_else(|| CellValue::String(text.to_string()))
+}
+
+fn push_reference(text: &mut String, e: &BytesRef) {
+    if let Ok(Some(c)) = e.resolve_char_ref() {
+        text.push(c);
+    } else if let Some(entity) = e
+        .decode()
+        .ok()
+        .and_then(|name| resolve_predefined_entity(&name))
+    {
+        text.push_str(entity);
+    }
+}
+
+/// Parses a W3CDTF or ODF date, converted to UTC if it has a timezone.
+fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|v| v.naive_utc())
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f"))
+        .ok()
+}
+
+/// A section of a property set [MS-OLEPS 2.21].
+struct PropertySection {
+    /// Properties in the order of the section
+    properties: Vec<(u32, CellValue)>,
+    /// Names of the properties of a user-defined section, read from the dictionary property
+    names: HashMap<u32, String>,
+}
+
+impl PropertySection {
+    fn get(&self, id: u32) -> Option<&CellValue> {
+        self.properties
+            .iter()
+            .find(|(property, _)| *property == id)
+            .map(|(_, value)| value)
+    }
+}
+
+fn read_property_stream<RS: Read + Seek>(
+    cfb: &mut CompoundFile<RS>,
+    name: &str,
+) -> io::Result<Vec<PropertySection>> {
+    if !cfb.is_stream(name) {
+        return Ok(Vec::new());
+    }
+    let mut data = Vec::new();
+    cfb.open_stream(name)?.read_to_end(&mut data)?;
+    Ok(read_property_set(&data))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        data.get(offset..offset + 8)?.try_into().ok()?,
+    ))
+}
+
+/// Reads the sections of a PropertySetStream [MS-OLEPS 2.21], unreadable ones are skipped.
+fn read_property_set(data: &[u8]) -> Vec<PropertySection> {
+    let count = read_u32(data, 24).unwrap_or(0) as usize;
+    (0..count.min(2))
+        .filter_map(|i| {
+            // FMTID followed by the offset of the section
+            let offset = read_u32(data, 28 + i * 20 + 16)? as usize;
+            read_property_section(data.get(offset..)?)
+        })
+        .collect()
+}
+
+fn read_property_section(section: &[u8]) -> Option<PropertySection> {
+    let count = read_u32(section, 4)? as usize;
+    let offsets: Vec<(u32, usize)> = (0..count)
+        .map_while(|i| {
+            Some((
+                read_u32(section, 8 + i * 8)?,
+                read_u32(section, 12 + i * 8)? as usize,
+            ))
+        })
+        .collect();
+    // PID_CODEPAGE, the code page of VT_LPSTR values and dictionary names
+    let code_page = offsets
+        .iter()
+        .find(|(id, _)| *id == 0x01)
+        .and_then(|(_, offset)| read_u16(section, offset + 4));
+    let encoding = code_page
+        .and_then(codepage::to_encoding)
+        .unwrap_or(encoding_rs::WINDOWS_1252);
+    let names = offsets
+        .iter()
+        .find(|(id, _)| *id == 0x00)
+        .and_then(|(_, offset)| {
+            read_dictionary(section, *offset, code_page == Some(1200), encoding)
+        })
+        .unwrap_or_default();
+    let properties = offsets
+        .into_iter()
+        .filter_map(|(id, offset)| Some((id, read_property(section, offset, encoding)?)))
+        .collect();
+    Some(PropertySection { properties, names })
+}
+
+/// Reads the Dictionary [MS-OLEPS 2.17] of a user-defined section.
+fn read_dictionary(
+    section: &[u8],
+    mut offset: usize,
+    unicode: bool,
+    encoding: &'static encoding_rs::Encoding,
+) -> Option<HashMap<u32, String>> {
+    let count = read_u32(section, offset)?;
+    offset += 4;
+    let mut names = HashMap::new();
+    for _ in 0..count {
+        let id = read_u32(section, offset)?;
+        let length = read_u32(section, offset + 4)? as usize;
+        offset += 8;
+        let (name, size) = if unicode {
+            // UTF-16 names are padded to a multiple of 4 bytes
+            let name = section.get(offset..offset + length * 2)?;
+            (
+                encoding_rs::UTF_16LE.decode(name).0,
+                (length * 2).next_multiple_of(4),
+            )
+        } else {
+            (
+                encoding.decode(section.get(offset..offset + length)?).0,
+                length,
+            )
+        };
+        names.insert(id, name.trim_end_matches('\0').to_string());
+        offset += size;
+    }
+    Some(names)
+}
+
+/// Reads a TypedPropertyValue [MS-OLEPS 2.15] of the types used by document properties.
+fn read_property(
+    section: &[u8],
+    offset: usize,
+    encoding: &'static encoding_rs::Encoding,
+) -> Option<CellValue> {
+    let typ = read_u16(section, offset)?;
+    let offset = offset + 4;
+    match typ {
+        // VT_I2
+        0x0002 => Some(CellValue::Int(read_u16(section, offset)? as i16 as i64)),
+        // VT_I4, VT_INT
+        0x0003 | 0x0016 => Some(CellValue::Int(read_u32(section, offset)? as i32 as i64)),
+        // VT_UI2
+        0x0012 => Some(CellValue::Int(read_u16(section, offset)? as i64)),
+        // VT_UI4, VT_UINT
+        0x0013 | 0x0017 => Some(CellValue::Int(read_u32(section, offset)? as i64)),
+        // VT_R4
+        0x0004 => Some(CellValue::Float(
+            f32::from_bits(read_u32(section, offset)?) as f64
+        )),
+        // VT_R8
+        0x0005 => Some(CellValue::Float(f64::from_bits(read_u64(section, offset)?))),
+        // VT_DATE, days since 1899-12-30
+        0x0007 => {
+            let days = f64::from_bits(read_u64(section, offset)?);
+            let milliseconds = (days * 86_400_000.0).round();
+            // the cast saturates, out of range values are dropped instead
+            if !milliseconds.is_finite() || milliseconds.abs() >= i64::MAX as f64 {
+                return None;
+            }
+            let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)?.and_hms_opt(0, 0, 0)?;
+            let value =
+                epoch.checked_add_signed(TimeDelta::try_milliseconds(milliseconds as i64)?)?;
+            Some(CellValue::DateTime(value))
+        }
+        // VT_BOOL
+        0x000B => Some(CellValue::Bool(read_u16(section, offset)? != 0)),
+        // VT_LPSTR
+        0x001E => {
+            let size = read_u32(section, offset)? as usize;
+            let bytes = section.get(offset + 4..offset + 4 + size)?;
+            let (value, _, _) = encoding.decode(bytes);
+            Some(CellValue::String(value.trim_end_matches('\0').to_string()))
+        }
+        // VT_LPWSTR
+        0x001F => {
+            let size = read_u32(section, offset)? as usize;
+            let bytes = section.get(offset + 4..offset + 4 + size * 2)?;
+            let (value, _, _) = encoding_rs::UTF_16LE.decode(bytes);
+            Some(CellValue::String(value.trim_end_matches('\0').to_string()))
+        }
+        // VT_FILETIME, 100-nanosecond intervals since 1601-01-01 UTC
+        0x0040 => {
+            let filetime = read_u64(section, offset)?;
+            if filetime == 0 {
+                return None;
+            }
+            let seconds = (filetime / 10_000_000) as i64 - 11_644_473_600;
+            let nanoseconds = (filetime % 10_000_000) as u32 * 100;
+            Some(CellValue::DateTime(
+                DateTime::from_timestamp(seconds, nanoseconds)?.naive_utc(),
+            ))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,178 @@
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek, SeekFrom};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use memmap2::Mmap;
+use pyo3::buffer::PyBuffer;
+use pyo3::prelude::*;
+use pyo3_file::PyFileLikeObject;
+
+/// Seekable reader over a Python file-like object.
+///
+/// Clones share the file object but keep their own position, so calamine can probe
+/// formats with cloned readers without buffering the whole file in memory.
+/// The file is sought before every read, as user code may move it between reads.
+#[derive(Clone)]
+pub struct PyFileReader {
+    file: Arc<Mutex<PyFileLikeObject>>,
+    position: u64,
+}
+
+impl PyFileReader {
+    /// Wraps a file, which is read from its start like the other workbook sources.
+    pub fn new(file: PyFileLikeObject) -> io::Result<Self> {
+        Ok(PyFileReader {
+            file: Arc::new(Mutex::new(file)),
+            position: 0,
+        })
+    }
+
+    fn lock(&self) -> io::Result<MutexGuard<'_, PyFileLikeObject>> {
+        self.file
+            .lock()
+            .map_err(|_| io::Error::other("file-like object lock is poisoned"))
+    }
+}
+
+impl Read for PyFileReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut file = self.lock()?;
+        file.seek(SeekFrom::Start(self.position))?;
+        let read = file.read(buf)?;
+        drop(file);
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for PyFileReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.position = match pos {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::Current(offset) => {
+                self.position.checked_add_signed(offset).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "invalid seek to a negative or overflowing position",
+                    )
+                })?
+            }
+            SeekFrom::End(_) => self.lock()?.seek(pos)?,
+        };
+        Ok(self.position)
+    }
+}
+
+/// Memory-mapped file contents, cheap to clone for format probing.
+#[derive(Clone)]
+pub struct MmapBytes(Arc<Mmap>);
+
+impl MmapBytes {
+    pub fn open(path: &str) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the file must not be truncated or modified while the workbook is open,
+        // this is documented for the `mmap` option.
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(MmapBytes(Arc::new(mmap)))
+    }
+}
+
+impl AsRef<[u8]> for MmapBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Contents of a Python object supporting the buffer protocol, shared without copying.
+///
+/// The buffer holds a reference to the exporting object, which keeps it alive
+/// (and prevents resizing) while the workbook is open.
+#[derive(Clone)]
+pub struct BufferBytes(Arc<PyBuffer<u8>>);
+
+impl BufferBytes {
+    /// Wraps a buffer, gives it back if it isn't C-contiguous.
+    pub fn new(buffer: PyBuffer<u8>) -> Result<Self, PyBuffer<u8>> {
+        if buffer.is_c_contiguous() {
+            Ok(BufferBytes(Arc::new(buffer)))
+        } else {
+            Err(buffer)
+        }
+    }
+}
+
+impl AsRef<[u8]> for BufferBytes {
+    fn as_ref(&self) -> &[u8] {
+        if self.0.len_bytes() == 0 {
+            return &[];
+        }
+        // SAFETY: the buffer is C-contiguous and stays valid until `PyBuffer` is dropped
+        unsafe { std::slice::from_raw_parts(self.0.buf_ptr() as *const u8, self.0.len_bytes()) }
+    }
+}
+
+/// Bytes a workbook was opened from, which can be read again independently of calamine,
+/// e.g. for parts of the file calamine doesn't expose.
+#[derive(Clone)]
+pub enum WorkbookSource {
+    Path(String),
+    Stream(PyFileReader),
+    Bytes(Arc<[u8]>),
+    Mmap(MmapBytes),
+    Buffer(BufferBytes),
+}
+
+impl WorkbookSource {
+    /// Opens a new reader positioned at the start of the workbook.
+    pub fn open(&self) -> io::Result<SourceReader> {
+        let mut reader = match self {
+            WorkbookSource::Path(path) => SourceReader::File(BufReader::new(File::open(path)?)),
+            WorkbookSource::Stream(reader) => SourceReader::Stream(reader.clone()),
+            WorkbookSource::Bytes(bytes) => SourceReader::Bytes(Cursor::new(bytes.clone())),
+            WorkbookSource::Mmap(bytes) => SourceReader::Mmap(Cursor::new(bytes.clone())),
+            WorkbookSource::Buffer(bytes) => SourceReader::Buffer(Cursor::new(bytes.clone())),
+        };
+        reader.seek(SeekFrom::Start(0))?;
+        Ok(reader)
+    }
+}
+
+pub enum SourceReader {
+    File(BufReader<File>),
+    Stream(PyFileReader),
+    Bytes(Cursor<Arc<[u8]>>),
+    Mmap(Cursor<MmapBytes>),
+    Buffer(Cursor<BufferBytes>),
+}
+
+impl Read for SourceReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            SourceReader::File(r) => r.read(buf),
+            SourceReader::Stream(r) => r.read(buf),
+            SourceReader::Bytes(r) => r.read(buf),
+            SourceReader::Mmap(r) => r.read(buf),
+            SourceReader::Buffer(r) => r.read(buf),
+        }
+    }
+}
+
+impl Seek for SourceReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            SourceReader::File(r) => r.seek(pos),
+            SourceReader::Stream(r) => r.seek(pos),
+            SourceReader::Bytes(r) => r.seek(pos),
+            SourceReader::Mmap(r) => r.seek(pos),
+            SourceReader::Buffer(r) => r.seek(pos),
+        }
+    }
+}
+
+/// Checks whether a file-like object can be read lazily, i.e. supports random access.
+pub fn is_seekable(filelike: &Bound<'_, PyAny>) -> bool {
+    match filelike.call_method0("seekable") {
+        Ok(seekable) => seekable.is_truthy().unwrap_or(false),
+        Err(_) => filelike.hasattr("seek").unwrap_or(false),
+    }
+}
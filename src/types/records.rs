@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use calamine::{Data, Range, Rows};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyString};
+
+use crate::types::cell::EmptyValue;
+
+/// Makes header names unique keys: a blank name becomes `Unnamed: {position}`
+/// and a repeated name gets the first free `_1`, `_2`... suffix.
+pub fn column_names(names: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    names
+        .into_iter()
+        .enumerate()
+        .map(|(position, name)| {
+            let name = if name.trim().is_empty() {
+                format!("Unnamed: {position}")
+            } else {
+                name
+            };
+            let mut unique = name.clone();
+            let mut suffix = 0;
+            while !seen.insert(unique.clone()) {
+                suffix += 1;
+                unique = format!("{name}_{suffix}");
+            }
+            unique
+        })
+        .collect()
+}
+
+/// Names of the header cells, see `column_names`.
+pub fn header_names(header: &[Data]) -> Vec<String> {
+    column_names(header.iter().map(Data::to_string))
+}
+
+fn keys(py: Python<'_>, names: Vec<String>) -> Vec<Py<PyString>> {
+    names
+        .iter()
+        .map(|name| PyString::new(py, name).unbind())
+        .collect()
+}
+
+fn row_to_dict<'py>(
+    py: Python<'py>,
+    keys: &[Py<PyString>],
+    row: &[Data],
+    empty_value: &EmptyValue,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for (key, value) in keys.iter().zip(row) {
+        dict.set_item(key.bind(py), empty_value.convert(py, value.into())?)?;
+    }
+    Ok(dict)
+}
+
+/// Converts the rows after `skip` to dicts keyed by `names`.
+pub fn to_records<'py>(
+    py: Python<'py>,
+    range: &Range<Data>,
+    names: Vec<String>,
+    skip: usize,
+    empty_value: &EmptyValue,
+) -> PyResult<Bound<'py, PyList>> {
+    let keys = keys(py, names);
+    let py_list = PyList::empty(py);
+    for row in range.rows().skip(skip) {
+        py_list.append(row_to_dict(py, &keys, row, empty_value)?)?;
+    }
+    Ok(py_list)
+}
+
+#[pyclass]
+pub struct CalamineDictIterator {
+    keys: Vec<Py<PyString>>,
+    empty_value: EmptyValue,
+    iter: Rows<'static, Data>,
+    #[allow(dead_code)]
+    range: Arc<Range<Data>>,
+}
+
+impl CalamineDictIterator {
+    /// Iterates over the rows after `skip` as dicts keyed by `names`.
+    pub fn new(
+        py: Python<'_>,
+        range: Arc<Range<Data>>,
+        names: Vec<String>,
+        skip: usize,
+        empty_value: EmptyValue,
+    ) -> CalamineDictIterator {
+        let mut iter = unsafe {
+            std::mem::transmute::<
+                calamine::Rows<'_, calamine::Data>,
+                calamine::Rows<'static, calamine::Data>,
+            >(range.rows())
+        };
+        if skip > 0 {
+            iter.nth(skip - 1);
+        }
+        CalamineDictIterator {
+            keys: keys(py, names),
+            empty_value,
+            iter,
+            range,
+        }
+    }
+}
+
+#[pymethods]
+impl CalamineDictIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<Bound<'_, PyDict>>> {
+        let py = slf.py();
+        let Some(row) = slf.iter.next() else {
+            return Ok(None);
+        };
+        row_to_dict(py, &slf.keys, row, &slf.empty_value).map(Some)
+    }
+}
@@ -0,0 +1,281 @@
+use std::sync::{Mutex, PoisonError};
+
+use calamine::{
+    Cell, CellType, Data, DataType, Error as CalamineCrateError, Range, Sheets, XlsxError,
+};
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+
+use crate::types::cell::EmptyValue;
+use crate::types::reader::SourceReader;
+use crate::types::usecols::UseCols;
+use crate::{CellValue, Error};
+
+/// Reads the next cell of a sheet in a column accepted by the filter, in file order.
+type NextCell =
+    Box<dyn FnMut(&dyn Fn(u32) -> bool) -> Result<Option<(u32, u32, CellValue)>, Error> + Send>;
+
+fn cell<T: CellType + DataType>(cell: Cell<T>) -> (u32, u32, CellValue) {
+    let (row, col) = cell.get_position();
+    (row, col, cell.get_value().into())
+}
+
+/// Rows of a sheet from `A1`, parsed from the sheet as they are consumed.
+#[pyclass]
+pub struct CalamineRowIterator {
+    /// Borrows `sheets` and is declared before it to be dropped first,
+    /// the lock only makes the iterator `Sync`
+    next_cell: Mutex<NextCell>,
+    #[allow(dead_code)]
+    sheets: Option<Box<Sheets<SourceReader>>>,
+    /// Width of the sheet dimension or of the widest row read so far, rows are padded to it
+    width: usize,
+    /// Columns to yield, `None` for all columns or a predicate which isn't called yet
+    columns: Option<Vec<u32>>,
+    /// A predicate called with the first row with a value
+    predicate: Option<UseCols>,
+    /// Index of the next row
+    position: u32,
+    /// First cell of a following row, read while completing the current one
+    pending: Option<(u32, u32, CellValue)>,
+    /// Number of empty rows to yield before `row`
+    gap: u32,
+    /// Completed row which wasn't yielded yet
+    row: Option<Vec<CellValue>>,
+    done: bool,
+    empty_value: EmptyValue,
+}
+
+impl CalamineRowIterator {
+    /// Streams the cells of an XLSX or XLSB sheet, `sheets` is a workbook opened for this
+    /// iterator only.
+    pub fn from_sheets(
+        sheets: Sheets<SourceReader>,
+        name: &str,
+        usecols: Option<UseCols>,
+        empty_value: EmptyValue,
+    ) -> Result<Self, Error> {
+        let mut sheets = Box::new(sheets);
+        // SAFETY: the cells reader needs a mutable borrow of the workbook for as long as the
+        // iterator lives, so the borrow is extended to `'static`. This is sound because:
+        // - the workbook is boxed, its address doesn't change when the iterator is moved;
+        // - the box is kept in `sheets` and never accessed again, so this is the only borrow;
+        // - `next_cell`, which owns the reader, is declared before `sheets` and is dropped
+        //   first, the borrow never outlives the workbook.
+        let workbook: &'static mut Sheets<SourceReader> =
+            unsafe { &mut *(sheets.as_mut() as *mut Sheets<SourceReader>) };
+        let (next_cell, dimensions): (NextCell, _) = match workbook {
+            Sheets::Xlsx(xlsx) => match xlsx.worksheet_cells_reader(name) {
+                Ok(mut reader) => {
+                    let dimensions = reader.dimensions();
+                    let next_cell = move |keep: &dyn Fn(u32) -> bool| loop {
+                        match reader
+                            .next_cell()
+                            .map_err(CalamineCrateError::Xlsx)
+                            .map_err(Error::Calamine)?
+                        {
+                            Some(value) if !keep(value.get_position().1) => continue,
+                            value => return Ok(value.map(cell)),
+                        }
+                    };
+                    (Box::new(next_cell), Some(dimensions))
+                }
+                // e.g. a chartsheet
+                Err(XlsxError::NotAWorksheet(_)) => {
+                    (Box::new(|_: &dyn Fn(u32) -> bool| Ok(None)), None)
+                }
+                Err(error) => return Err(Error::Calamine(CalamineCrateError::Xlsx(error))),
+            },
+            Sheets::Xlsb(xlsb) => {
+                let mut reader = xlsb
+                    .worksheet_cells_reader(name)
+                    .map_err(CalamineCrateError::Xlsb)
+                    .map_err(Error::Calamine)?;
+                let dimensions = reader.dimensions();
+                let next_cell = move |keep: &dyn Fn(u32) -> bool| loop {
+                    match reader
+                        .next_cell()
+                        .map_err(CalamineCrateError::Xlsb)
+                        .map_err(Error::Calamine)?
+                    {
+                        Some(value) if !keep(value.get_position().1) => continue,
+                        value => return Ok(value.map(cell)),
+                    }
+                };
+                (Box::new(next_cell), Some(dimensions))
+            }
+            Sheets::Xls(_) | Sheets::Ods(_) => unreachable!(),
+        };
+        // the dimension is optional, `A1` alone is written for an empty sheet
+        let width = dimensions
+            .filter(|d| d.end != (0, 0))
+            .map_or(0, |d| d.end.1 as usize + 1);
+        Ok(Self::new(
+            next_cell,
+            Some(sheets),
+            width,
+            usecols,
+            empty_value,
+        ))
+    }
+
+    pub fn from_range(
+        range: Range<Data>,
+        usecols: Option<UseCols>,
+        empty_value: EmptyValue,
+    ) -> Self {
+        let width = range.end().map_or(0, |end| end.1 as usize + 1);
+        let start = range.start().unwrap_or_default();
+        let mut cells = range
+            .used_cells()
+            .map(|(row, col, value)| (start.0 + row as u32, start.1 + col as u32, value.into()))
+            .collect::<Vec<_>>()
+            .into_iter();
+        let next_cell = move |keep: &dyn Fn(u32) -> bool| Ok(cells.find(|cell| keep(cell.1)));
+        Self::new(Box::new(next_cell), None, width, usecols, empty_value)
+    }
+
+    fn new(
+        next_cell: NextCell,
+        sheets: Option<Box<Sheets<SourceReader>>>,
+        width: usize,
+        usecols: Option<UseCols>,
+        empty_value: EmptyValue,
+    ) -> Self {
+        let (columns, predicate) = match usecols {
+            Some(UseCols::Indices(indices)) => {
+                let columns = indices
+                    .into_iter()
+                    .filter(|&col| width == 0 || (col as usize) < width)
+                    .collect();
+                (Some(columns), None)
+            }
+            predicate => (None, predicate),
+        };
+        CalamineRowIterator {
+            next_cell: Mutex::new(next_cell),
+            sheets,
+            width,
+            columns,
+            predicate,
+            position: 0,
+            pending: None,
+            gap: 0,
+            row: None,
+            done: false,
+            empty_value,
+        }
+    }
+
+    /// Returns the next row, reading the next row with a value when the empty rows before
+    /// it are yielded.
+    fn next_row(&mut self) -> Result<Option<Vec<CellValue>>, Error> {
+        if self.gap == 0 && self.row.is_none() && !self.done {
+            self.read_row()?;
+        }
+        if self.gap > 0 {
+            self.gap -= 1;
+            return Ok(Some(self.pad(Vec::new())));
+        }
+        Ok(self.row.take().map(|row| self.pad(row)))
+    }
+
+    /// Reads the cells of the next row with a value and counts the empty rows before it.
+    fn read_row(&mut self) -> Result<(), Error> {
+        let mut row = Vec::new();
+        let mut index = None;
+        let columns = &self.columns;
+        let keep = |col: u32| {
+            columns
+                .as_ref()
+                .is_none_or(|c| c.binary_search(&col).is_ok())
+        };
+        loop {
+            let cell = match self.pending.take() {
+                Some(cell) => Some(cell),
+                None => self
+                    .next_cell
+                    .get_mut()
+                    .unwrap_or_else(PoisonError::into_inner)(&keep)?,
+            };
+            let Some((cell_row, col, value)) = cell else {
+                self.done = true;
+                break;
+            };
+            if matches!(value, CellValue::Empty) {
+                continue;
+            }
+            match index {
+                // cells of a row written again after a later row are dropped
+                _ if cell_row < self.position => continue,
+                Some(index) if cell_row != index => {
+                    self.pending = Some((cell_row, col, value));
+                    break;
+                }
+                _ => index = Some(cell_row),
+            }
+            let col = col as usize;
+            if row.len() <= col {
+                row.resize(col + 1, CellValue::Empty);
+            }
+            row[col] = value;
+        }
+        let Some(index) = index else {
+            return Ok(());
+        };
+        self.width = self.width.max(row.len());
+        self.gap = index - self.position;
+        self.row = Some(row);
+        self.position = index + 1;
+        Ok(())
+    }
+
+    fn pad(&self, mut row: Vec<CellValue>) -> Vec<CellValue> {
+        if row.len() < self.width {
+            row.resize(self.width, CellValue::Empty);
+        }
+        row
+    }
+
+    /// Takes the cells of the selected columns, the columns of a predicate are chosen
+    /// from the first row with a value.
+    fn select(&mut self, py: Python<'_>, mut row: Vec<CellValue>) -> PyResult<Vec<CellValue>> {
+        if let Some(predicate) = self.predicate.take() {
+            // the first row with a value is already read when empty rows before it are yielded
+            let header = match self.row.clone() {
+                Some(header) => self.pad(header),
+                None => row.clone(),
+            };
+            let positions = predicate.positions(py, 0, header.len(), || header)?;
+            self.columns = Some(positions.into_iter().map(|col| col as u32).collect());
+        }
+        Ok(match &self.columns {
+            Some(columns) => columns
+                .iter()
+                .map(|&col| {
+                    row.get_mut(col as usize).map_or(CellValue::Empty, |value| {
+                        std::mem::replace(value, CellValue::Empty)
+                    })
+                })
+                .collect(),
+            None => row,
+        })
+    }
+}
+
+#[pymethods]
+impl CalamineRowIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<Bound<'_, PyList>>> {
+        let py = slf.py();
+        let this = &mut *slf;
+        let Some(row) = py.detach(|| this.next_row())? else {
+            return Ok(None);
+        };
+        let row = slf.select(py, row)?;
+        slf.empty_value.list(py, row.into_iter()).map(Some)
+    }
+}
@@ -4,8 +4,18 @@ use std::sync::Arc;
 use calamine::{Data, Dimensions, Range, Rows, SheetType, SheetVisible};
 use pyo3::class::basic::CompareOp;
 use pyo3::prelude::*;
-use pyo3::types::PyList;
-
+use pyo3::types::{PyCapsule, PyDict, PyList};
+use pyo3::IntoPyObjectExt;
+
+use crate::types::arrow::arrow_c_stream;
+use crate::types::batches::{BatchOptions, CalamineBatchIterator};
+use crate::types::cell::EmptyValue;
+use crate::types::columns::{columns, Column};
+use crate::types::interchange::CalamineInterchangeFrame;
+use crate::types::numpy::column_to_ndarray;
+use crate::types::pandas::to_dataframe;
+use crate::types::records::{column_names, header_names, to_records, CalamineDictIterator};
+use crate::types::usecols::UseCols;
 use crate::CellValue;
 
 #[pyclass(eq, eq_int, from_py_object)]
@@ -127,6 +137,10 @@ impl SheetMetadata {
         let visible = SheetVisibleEnum::from(visible);
         SheetMetadata { name, typ, visible }
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 #[pyclass]
@@ -149,6 +163,14 @@ impl CalamineSheet {
             merged_cell_ranges,
         }
     }
+
+    /// Column names from a row of the sheet, blank names if there is no such row.
+    fn header_names(&self, header_row: usize) -> Vec<String> {
+        match self.range.rows().nth(header_row) {
+            Some(header) => header_names(header),
+            None => column_names(vec![String::new(); self.range.width()]),
+        }
+    }
 }
 
 #[pymethods]
@@ -187,12 +209,15 @@ impl CalamineSheet {
         self.range.end()
     }
 
-    #[pyo3(signature = (skip_empty_area=true, nrows=None))]
-    fn to_python(
-        slf: PyRef<'_, Self>,
+    #[pyo3(signature = (skip_empty_area=true, nrows=None, usecols=None, empty_value=EmptyValue::default()))]
+    fn to_python<'py>(
+        slf: PyRef<'py, Self>,
         skip_empty_area: bool,
         nrows: Option<u32>,
-    ) -> PyResult<Bound<'_, PyList>> {
+        usecols: Option<&Bound<'py, PyAny>>,
+        empty_value: EmptyValue,
+    ) -> PyResult<Bound<'py, PyList>> {
+        let usecols = usecols.map(UseCols::extract).transpose()?;
         let nrows = match nrows {
             Some(nrows) => nrows,
             None => slf.range.end().map_or(0, |end| end.0 + 1),
@@ -211,8 +236,31 @@ impl CalamineSheet {
 
         let py_list = PyList::empty(slf.py());
 
+        let positions = match (&usecols, range.rows().next()) {
+            (Some(usecols), Some(header)) => Some(usecols.positions(
+                slf.py(),
+                range.start().map_or(0, |start| start.1),
+                header.len(),
+                || {
+                    header
+                        .iter()
+                        .map(<&Data as Into<CellValue>>::into)
+                        .collect()
+                },
+            )?),
+            _ => None,
+        };
+
         for row in range.rows().take(nrows as usize) {
-            let py_row = PyList::new(slf.py(), row.iter().map(<&Data as Into<CellValue>>::into))?;
+            let py_row = match &positions {
+                Some(positions) => empty_value.list(
+                    slf.py(),
+                    positions.iter().map(|&col| CellValue::from(&row[col])),
+                )?,
+                None => {
+                    empty_value.list(slf.py(), row.iter().map(<&Data as Into<CellValue>>::into))?
+                }
+            };
 
             py_list.append(py_row)?;
         }
@@ -220,8 +268,157 @@ impl CalamineSheet {
         Ok(py_list)
     }
 
-    fn iter_rows(&self) -> CalamineCellIterator {
-        CalamineCellIterator::from_range(Arc::clone(&self.range))
+    #[pyo3(signature = (usecols=None, empty_value=EmptyValue::default()))]
+    fn iter_rows(
+        &self,
+        py: Python<'_>,
+        usecols: Option<&Bound<'_, PyAny>>,
+        empty_value: EmptyValue,
+    ) -> PyResult<CalamineCellIterator> {
+        let usecols = usecols.map(UseCols::extract).transpose()?;
+        CalamineCellIterator::from_range(py, Arc::clone(&self.range), usecols.as_ref(), empty_value)
+    }
+
+    #[pyo3(signature = (header_row=0, empty_value=EmptyValue::default()))]
+    fn iter_dicts(
+        &self,
+        py: Python<'_>,
+        header_row: usize,
+        empty_value: EmptyValue,
+    ) -> CalamineDictIterator {
+        let names = self.header_names(header_row);
+        CalamineDictIterator::new(
+            py,
+            Arc::clone(&self.range),
+            names,
+            header_row + 1,
+            empty_value,
+        )
+    }
+
+    #[pyo3(signature = (header_row=0, empty_value=EmptyValue::default()))]
+    fn to_records<'py>(
+        &self,
+        py: Python<'py>,
+        header_row: usize,
+        empty_value: EmptyValue,
+    ) -> PyResult<Bound<'py, PyList>> {
+        let names = self.header_names(header_row);
+        to_records(py, &self.range, names, header_row + 1, &empty_value)
+    }
+
+    /// Columns of the rows after the first one, named by it.
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        // the types come from the cells, casting to a requested schema is left to the consumer
+        let _ = requested_schema;
+        let names = self.header_names(0);
+        let range = Arc::clone(&self.range);
+        let columns = py.detach(|| columns(&range, 1));
+        arrow_c_stream(py, names, columns)
+    }
+
+    fn to_arrow<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyAny>> {
+        slf.py().import("pyarrow")?.call_method1("table", (slf,))
+    }
+
+    /// Columns of the rows after `header_row`, named by it or by their positions.
+    #[pyo3(signature = (nan_as_null=false, allow_copy=true, header_row=Some(0)))]
+    fn __dataframe__(
+        &self,
+        nan_as_null: bool,
+        allow_copy: bool,
+        header_row: Option<usize>,
+    ) -> CalamineInterchangeFrame {
+        // the columns are always copied from the cells, NaN isn't a null
+        let _ = (nan_as_null, allow_copy);
+        let names = match header_row {
+            Some(header_row) => self.header_names(header_row),
+            None => (0..self.range.width()).map(|i| i.to_string()).collect(),
+        };
+        let skip = header_row.map_or(0, |header_row| header_row + 1);
+        CalamineInterchangeFrame::new(Arc::clone(&self.range), names, skip)
+    }
+
+    /// Batches of the rows after the first one, named by it.
+    #[pyo3(signature = (columns=None, n_rows=None, batch_size=None, infer_schema_length=None))]
+    fn iter_batches(
+        &self,
+        py: Python<'_>,
+        columns: Option<Vec<String>>,
+        n_rows: Option<usize>,
+        batch_size: Option<usize>,
+        infer_schema_length: Option<usize>,
+    ) -> PyResult<CalamineBatchIterator> {
+        let names = self.header_names(0);
+        let options = BatchOptions {
+            columns,
+            n_rows,
+            batch_size,
+            infer_schema_length,
+        };
+        CalamineBatchIterator::new(py, Arc::clone(&self.range), names, 1, options)
+    }
+
+    /// All cells of the sheet in one array with a type for all of them.
+    #[pyo3(signature = (dtype=None))]
+    fn to_numpy<'py>(
+        &self,
+        py: Python<'py>,
+        dtype: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let range = Arc::clone(&self.range);
+        let column = py.detach(|| {
+            Column::from_cells(range.cells().map(|(_, _, value)| value.into()).collect())
+        });
+        let array = column_to_ndarray(py, column)?
+            .call_method1("reshape", ((range.height(), range.width()),))?;
+        match dtype {
+            Some(dtype) => array.call_method1("astype", (dtype,)),
+            None => Ok(array),
+        }
+    }
+
+    /// Columns of the rows after the first one, named by it.
+    fn to_columns<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let names = self.header_names(0);
+        let range = Arc::clone(&self.range);
+        let columns = py.detach(|| columns(&range, 1));
+        let dict = PyDict::new(py);
+        for (name, column) in names.into_iter().zip(columns) {
+            dict.set_item(name, column_to_ndarray(py, column)?)?;
+        }
+        Ok(dict)
+    }
+
+    /// Columns of the rows after `header`, named by it or by their positions.
+    #[pyo3(signature = (header=Some(0), index_col=None, dtype=None, parse_dates=true))]
+    fn to_pandas<'py>(
+        &self,
+        py: Python<'py>,
+        header: Option<usize>,
+        index_col: Option<Bound<'py, PyAny>>,
+        dtype: Option<Bound<'py, PyAny>>,
+        parse_dates: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let names = match header {
+            Some(header) => self
+                .header_names(header)
+                .into_iter()
+                .map(|name| name.into_bound_py_any(py))
+                .collect::<PyResult<_>>()?,
+            None => (0..self.range.width())
+                .map(|position| position.into_bound_py_any(py))
+                .collect::<PyResult<_>>()?,
+        };
+        let skip = header.map_or(0, |header| header + 1);
+        let range = Arc::clone(&self.range);
+        let columns = py.detach(|| columns(&range, skip));
+        to_dataframe(py, names, columns, index_col, dtype, parse_dates)
     }
 
     #[getter]
@@ -236,21 +433,42 @@ impl CalamineSheet {
 pub struct CalamineCellIterator {
     position: u32,
     start: (u32, u32),
-    empty_row: Vec<CellValue>,
+    /// Width of the empty rows before the range
+    width: usize,
+    empty_value: EmptyValue,
+    /// Positions of the columns selected by `usecols` in a row
+    positions: Option<Vec<usize>>,
     iter: Rows<'static, Data>,
     #[allow(dead_code)]
     range: Arc<Range<Data>>,
 }
 
 impl CalamineCellIterator {
-    fn from_range(range: Arc<Range<Data>>) -> CalamineCellIterator {
-        let empty_row = (0..range.width())
-            .map(|_| CellValue::String("".to_string()))
-            .collect();
-        CalamineCellIterator {
-            empty_row,
+    fn from_range(
+        py: Python<'_>,
+        range: Arc<Range<Data>>,
+        usecols: Option<&UseCols>,
+        empty_value: EmptyValue,
+    ) -> PyResult<CalamineCellIterator> {
+        let start = range.start().unwrap();
+        let positions = usecols
+            .map(|usecols| {
+                // the header is the first yielded row, empty when the range doesn't start at it
+                usecols.positions(py, start.1, range.width(), || match range.rows().next() {
+                    Some(row) if start.0 == 0 => {
+                        row.iter().map(<&Data as Into<CellValue>>::into).collect()
+                    }
+                    _ => vec![CellValue::String("".to_string()); range.width()],
+                })
+            })
+            .transpose()?;
+        let width = positions.as_ref().map_or(range.width(), Vec::len);
+        Ok(CalamineCellIterator {
+            width,
+            empty_value,
             position: 0,
-            start: range.start().unwrap(),
+            start,
+            positions,
             iter: unsafe {
                 std::mem::transmute::<
                     calamine::Rows<'_, calamine::Data>,
@@ -258,7 +476,7 @@ impl CalamineCellIterator {
                 >(range.rows())
             },
             range,
-        }
+        })
     }
 }
 
@@ -271,12 +489,21 @@ impl CalamineCellIterator {
     fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<Bound<'_, PyList>>> {
         slf.position += 1;
         if slf.position > slf.start.0 {
-            slf.iter
-                .next()
-                .map(|row| PyList::new(slf.py(), row.iter().map(<&Data as Into<CellValue>>::into)))
-                .transpose()
+            let row = slf.iter.next();
+            match (row, &slf.positions) {
+                (Some(row), Some(positions)) => Some(slf.empty_value.list(
+                    slf.py(),
+                    positions.iter().map(|&col| CellValue::from(&row[col])),
+                )),
+                (row, _) => row.map(|row| {
+                    slf.empty_value
+                        .list(slf.py(), row.iter().map(<&Data as Into<CellValue>>::into))
+                }),
+            }
+            .transpose()
         } else {
-            Some(PyList::new(slf.py(), slf.empty_row.clone())).transpose()
+            let empty_row = std::iter::repeat_n(CellValue::Empty, slf.width);
+            Some(slf.empty_value.list(slf.py(), empty_row)).transpose()
         }
     }
 }
@@ -2,8 +2,14 @@ use std::sync::Arc;
 
 use calamine::{Data, Range};
 use pyo3::prelude::*;
-use pyo3::types::PyList;
+use pyo3::types::{PyCapsule, PyList};
 
+use crate::types::arrow::arrow_c_stream;
+use crate::types::batches::{BatchOptions, CalamineBatchIterator};
+use crate::types::cell::EmptyValue;
+use crate::types::columns::columns;
+use crate::types::interchange::CalamineInterchangeFrame;
+use crate::types::records::{column_names, to_records, CalamineDictIterator};
 use crate::CellValue;
 
 #[pyclass(from_py_object)]
@@ -55,17 +61,79 @@ impl CalamineTable {
         self.range.end()
     }
 
-    fn to_python(slf: PyRef<'_, Self>) -> PyResult<Bound<'_, PyList>> {
+    #[pyo3(signature = (empty_value=EmptyValue::default()))]
+    fn to_python(slf: PyRef<'_, Self>, empty_value: EmptyValue) -> PyResult<Bound<'_, PyList>> {
         let range = Arc::clone(&slf.range);
 
         let py_list = PyList::empty(slf.py());
 
         for row in range.rows() {
-            let py_row = PyList::new(slf.py(), row.iter().map(<&Data as Into<CellValue>>::into))?;
+            let py_row =
+                empty_value.list(slf.py(), row.iter().map(<&Data as Into<CellValue>>::into))?;
 
             py_list.append(py_row)?;
         }
 
         Ok(py_list)
     }
+
+    #[pyo3(signature = (empty_value=EmptyValue::default()))]
+    fn iter_dicts(&self, py: Python<'_>, empty_value: EmptyValue) -> CalamineDictIterator {
+        let names = column_names(self.columns.iter().cloned());
+        CalamineDictIterator::new(py, Arc::clone(&self.range), names, 0, empty_value)
+    }
+
+    #[pyo3(signature = (empty_value=EmptyValue::default()))]
+    fn to_records<'py>(
+        &self,
+        py: Python<'py>,
+        empty_value: EmptyValue,
+    ) -> PyResult<Bound<'py, PyList>> {
+        let names = column_names(self.columns.iter().cloned());
+        to_records(py, &self.range, names, 0, &empty_value)
+    }
+
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        // the types come from the cells, casting to a requested schema is left to the consumer
+        let _ = requested_schema;
+        let names = column_names(self.columns.iter().cloned());
+        let range = Arc::clone(&self.range);
+        let columns = py.detach(|| columns(&range, 0));
+        arrow_c_stream(py, names, columns)
+    }
+
+    fn to_arrow<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyAny>> {
+        slf.py().import("pyarrow")?.call_method1("table", (slf,))
+    }
+
+    #[pyo3(signature = (nan_as_null=false, allow_copy=true))]
+    fn __dataframe__(&self, nan_as_null: bool, allow_copy: bool) -> CalamineInterchangeFrame {
+        let _ = (nan_as_null, allow_copy);
+        let names = column_names(self.columns.iter().cloned());
+        CalamineInterchangeFrame::new(Arc::clone(&self.range), names, 0)
+    }
+
+    #[pyo3(signature = (columns=None, n_rows=None, batch_size=None, infer_schema_length=None))]
+    fn iter_batches(
+        &self,
+        py: Python<'_>,
+        columns: Option<Vec<String>>,
+        n_rows: Option<usize>,
+        batch_size: Option<usize>,
+        infer_schema_length: Option<usize>,
+    ) -> PyResult<CalamineBatchIterator> {
+        let names = column_names(self.columns.iter().cloned());
+        let options = BatchOptions {
+            columns,
+            n_rows,
+            batch_size,
+            infer_schema_length,
+        };
+        CalamineBatchIterator::new(py, Arc::clone(&self.range), names, 0, options)
+    }
 }
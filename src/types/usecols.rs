@@ -0,0 +1,80 @@
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+
+use crate::types::defined_name::parse_column;
+use crate::CellValue;
+
+/// Columns selected by the `usecols` argument.
+pub enum UseCols {
+    /// Zero-based sheet columns, sorted and without duplicates
+    Indices(Vec<u32>),
+    /// Called with the first row value of each column, the column is kept when it returns true
+    Predicate(Py<PyAny>),
+}
+
+impl UseCols {
+    pub fn extract(usecols: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let mut indices = if usecols.is_instance_of::<PyString>() {
+            parse_columns(&usecols.extract::<String>()?)?
+        } else if usecols.is_callable() {
+            return Ok(UseCols::Predicate(usecols.clone().unbind()));
+        } else {
+            usecols.extract::<Vec<u32>>().map_err(|_| {
+                PyTypeError::new_err(
+                    "usecols must be a str, a list of column indices or a callable",
+                )
+            })?
+        };
+        indices.sort_unstable();
+        indices.dedup();
+        Ok(UseCols::Indices(indices))
+    }
+
+    /// Positions of the selected columns in rows of `width` cells starting at the sheet
+    /// column `first_col`, `header` gives the first row for a predicate.
+    pub fn positions(
+        &self,
+        py: Python<'_>,
+        first_col: u32,
+        width: usize,
+        header: impl FnOnce() -> Vec<CellValue>,
+    ) -> PyResult<Vec<usize>> {
+        match self {
+            UseCols::Indices(indices) => Ok(indices
+                .iter()
+                .filter_map(|col| col.checked_sub(first_col))
+                .map(|col| col as usize)
+                .filter(|&col| col < width)
+                .collect()),
+            UseCols::Predicate(predicate) => {
+                let mut positions = Vec::new();
+                for (position, value) in header().into_iter().enumerate() {
+                    if predicate.bind(py).call1((value,))?.is_truthy()? {
+                        positions.push(position);
+                    }
+                }
+                Ok(positions)
+            }
+        }
+    }
+}
+
+/// Parses columns like `A:C,F` into indices.
+fn parse_columns(usecols: &str) -> PyResult<Vec<u32>> {
+    let invalid = || PyValueError::new_err(format!("Invalid usecols: '{usecols}'"));
+    let mut indices = Vec::new();
+    for part in usecols.split(',') {
+        let (first, last) = match part.split_once(':') {
+            Some((first, last)) => (first, last),
+            None => (part, part),
+        };
+        let first = parse_column(first.trim()).ok_or_else(invalid)?;
+        let last = parse_column(last.trim()).ok_or_else(invalid)?;
+        if first > last {
+            return Err(invalid());
+        }
+        indices.extend(first..=last);
+    }
+    Ok(indices)
+}
@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::{self, Cursor, Read, Seek};
+
+use calamine::vba::VbaProject as CalamineVbaProject;
+use calamine::Error as CalamineCrateError;
+use cfb::CompoundFile;
+use pyo3::prelude::*;
+use zip::ZipArchive;
+
+use crate::Error;
+
+#[pyclass(eq, eq_int, from_py_object)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VbaModuleTypeEnum {
+    /// Standard (procedural) module
+    Standard,
+    /// Class module
+    Class,
+    /// Document module of the workbook or a sheet
+    Document,
+    /// Designer module, e.g. a user form
+    Designer,
+}
+
+impl Display for VbaModuleTypeEnum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "VbaModuleTypeEnum.{self:?}")
+    }
+}
+
+#[pyclass(from_py_object)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct VbaModule {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    typ: Option<VbaModuleTypeEnum>,
+    #[pyo3(get)]
+    source: String,
+}
+
+#[pymethods]
+impl VbaModule {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "VbaModule(name='{}', typ={})",
+            self.name,
+            self.typ.map_or("None".to_string(), |typ| typ.to_string()),
+        ))
+    }
+}
+
+#[pyclass(from_py_object)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct VbaReference {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    description: String,
+    #[pyo3(get)]
+    path: String,
+}
+
+#[pymethods]
+impl VbaReference {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "VbaReference(name='{}', path='{}')",
+            self.name, self.path
+        ))
+    }
+}
+
+#[pyclass(from_py_object)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct VbaProject {
+    #[pyo3(get)]
+    modules: Vec<VbaModule>,
+    #[pyo3(get)]
+    references: Vec<VbaReference>,
+}
+
+#[pymethods]
+impl VbaProject {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "VbaProject(modules={}, references={})",
+            self.modules.len(),
+            self.references.len()
+        ))
+    }
+
+    #[getter]
+    fn module_names(&self) -> Vec<String> {
+        self.modules.iter().map(|m| m.name.clone()).collect()
+    }
+
+    fn get_module(&self, name: &str) -> Option<VbaModule> {
+        self.modules.iter().find(|m| m.name == name).cloned()
+    }
+}
+
+impl VbaProject {
+    /// Decodes the modules of calamine's project, `module_types` are read by `module_types_*`.
+    pub fn new(
+        project: &CalamineVbaProject,
+        module_types: &HashMap<String, VbaModuleTypeEnum>,
+    ) -> Result<Self, Error> {
+        let modules = project
+            .get_module_names()
+            .into_iter()
+            .map(|name| {
+                Ok(VbaModule {
+                    name: name.to_string(),
+                    typ: module_types.get(name).copied(),
+                    source: project
+                        .get_module(name)
+                        .map_err(CalamineCrateError::Vba)
+                        .map_err(Error::Calamine)?,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        let references = project
+            .get_references()
+            .iter()
+            .map(|r| VbaReference {
+                name: r.name.clone(),
+                description: r.description.clone(),
+                path: r.path.to_string_lossy().into_owned(),
+            })
+            .collect();
+        Ok(VbaProject {
+            modules,
+            references,
+        })
+    }
+}
+
+/// Reads the module types of the VBA project of an XLS workbook.
+pub fn module_types_xls<RS: Read + Seek>(
+    reader: RS,
+) -> io::Result<HashMap<String, VbaModuleTypeEnum>> {
+    module_types(&mut CompoundFile::open(reader)?, "_VBA_PROJECT_CUR/")
+}
+
+/// Reads the module types of the VBA project of an XLSX, XLSM or XLSB workbook.
+pub fn module_types_xlsx<RS: Read + Seek>(
+    reader: RS,
+) -> io::Result<HashMap<String, VbaModuleTypeEnum>> {
+    let mut zip = ZipArchive::new(reader).map_err(io::Error::other)?;
+    let mut project = Vec::new();
+    match zip.by_name("xl/vbaProject.bin") {
+        Ok(mut file) => file.read_to_end(&mut project)?,
+        Err(_) => return Ok(HashMap::new()),
+    };
+    module_types(&mut CompoundFile::open(Cursor::new(project))?, "")
+}
+
+/// Module types are only distinguished by the PROJECT stream [MS-OVBA 2.3.1],
+/// which is encoded with the code page of the dir stream.
+fn module_types<RS: Read + Seek>(
+    cfb: &mut CompoundFile<RS>,
+    root: &str,
+) -> io::Result<HashMap<String, VbaModuleTypeEnum>> {
+    let mut dir = Vec::new();
+    cfb.open_stream(format!("{root}VBA/dir"))?
+        .read_to_end(&mut dir)?;
+    let encoding = read_code_page(&decompress(&dir)?)
+        .and_then(codepage::to_encoding)
+        .unwrap_or(encoding_rs::WINDOWS_1252);
+
+    let mut project = Vec::new();
+    cfb.open_stream(format!("{root}PROJECT"))?
+        .read_to_end(&mut project)?;
+    let (project, _, _) = encoding.decode(&project);
+
+    let mut types = HashMap::new();
+    // ProjectModules are followed by the [Host Extender Info] and [Workspace] sections
+    for line in project.lines().take_while(|line| !line.starts_with('[')) {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (name, typ) = match key {
+            "Module" => (value, VbaModuleTypeEnum::Standard),
+            "Class" => (value, VbaModuleTypeEnum::Class),
+            // Document=<name>/&H<version>
+            "Document" => (
+                value.rsplit_once('/').map_or(value, |(name, _)| name),
+                VbaModuleTypeEnum::Document,
+            ),
+            "BaseClass" => (value, VbaModuleTypeEnum::Designer),
+            _ => continue,
+        };
+        types.insert(name.to_string(), typ);
+    }
+    Ok(types)
+}
+
+/// Reads PROJECTCODEPAGE [MS-OVBA 2.3.4.2.1.4], which precedes the records with irregular sizes.
+fn read_code_page(dir: &[u8]) -> Option<u16> {
+    let mut offset = 0;
+    while let Some(header) = dir.get(offset..offset + 6) {
+        let id = u16::from_le_bytes([header[0], header[1]]);
+        let size = u32::from_le_bytes([header[2], header[3], header[4], header[5]]) as usize;
+        let data = dir.get(offset + 6..offset + 6 + size)?;
+        match id {
+            0x0003 if size == 2 => return Some(u16::from_le_bytes([data[0], data[1]])),
+            // PROJECTSYSKIND, PROJECTCOMPATVERSION, PROJECTLCID, PROJECTLCIDINVOKE
+            0x0001 | 0x004A | 0x0002 | 0x0014 => offset += 6 + size,
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Maximum decompressed size of a chunk [MS-OVBA 2.4.1.1.4].
+const CHUNK_SIZE: usize = 4096;
+
+/// Decompresses a CompressedContainer [MS-OVBA 2.4.1].
+fn decompress(data: &[u8]) -> io::Result<Vec<u8>> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid compressed VBA stream");
+    if data.first() != Some(&0x01) {
+        return Err(invalid());
+    }
+    let mut output = Vec::with_capacity(data.len() * 2);
+    let mut offset = 1;
+    while let Some(header) = data.get(offset..offset + 2) {
+        let header = u16::from_le_bytes([header[0], header[1]]);
+        let end = (offset + (header & 0x0FFF) as usize + 3).min(data.len());
+        let compressed = header & 0x8000 != 0;
+        offset += 2;
+        let chunk_start = output.len();
+        if !compressed {
+            output.extend_from_slice(&data[offset..end]);
+            offset = end;
+            continue;
+        }
+        while offset < end {
+            let flags = data[offset];
+            offset += 1;
+            for bit in 0..8 {
+                if offset >= end {
+                    break;
+                }
+                if flags & (1 << bit) == 0 {
+                    output.push(data[offset]);
+                    offset += 1;
+                    continue;
+                }
+                let token =
+                    u16::from_le_bytes([data[offset], *data.get(offset + 1).ok_or_else(invalid)?]);
+                offset += 2;
+                let difference = output.len() - chunk_start;
+                let bit_count = (usize::BITS - difference.saturating_sub(1).leading_zeros()).max(4);
+                let length_mask = 0xFFFF >> bit_count;
+                let length = (token & length_mask) as usize + 3;
+                let copy_offset = (token >> (16 - bit_count)) as usize + 1;
+                if copy_offset > difference {
+                    return Err(invalid());
+                }
+                for _ in 0..length {
+                    output.push(output[output.len() - copy_offset]);
+                }
+            }
+            // checked for every flag group, the bit count of the copy tokens
+            // only fits in 16 bits within a chunk
+            if output.len() - chunk_start > CHUNK_SIZE {
+                return Err(invalid());
+            }
+        }
+    }
+    Ok(output)
+}
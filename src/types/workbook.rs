@@ -1,55 +1,369 @@
 use std::fs::File;
-use std::io::{BufReader, Cursor, Read};
-use std::path::PathBuf;
+use std::io::{BufReader, Cursor, Read, Seek};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
 use calamine::{
-    open_workbook_auto, open_workbook_auto_from_rs, Error as CalamineCrateError, Reader, Sheets,
+    Cell, CellType, Data, DataType, Error as CalamineCrateError, HeaderRow, Ods, Range, Reader,
+    Sheets, Xls, Xlsb, Xlsx, XlsxError,
 };
-use pyo3::exceptions::PyTypeError;
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyType;
+use pyo3::types::{IntoPyDict, PyDict, PyType};
 use pyo3_file::PyFileLikeObject;
 
-use crate::{CalamineSheet, CalamineTable, Error, SheetMetadata, WorksheetNotFound};
+use crate::types::cell::EmptyValue;
+use crate::types::crypto::decrypt;
+use crate::types::defined_name::CellReference;
+use crate::types::format::WorkbookInfo;
+use crate::types::properties::{
+    custom_properties_ods, custom_properties_xls, custom_properties_xlsx,
+};
+use crate::types::reader::{is_seekable, BufferBytes, MmapBytes, PyFileReader, WorkbookSource};
+use crate::types::rows::CalamineRowIterator;
+use crate::types::usecols::UseCols;
+use crate::types::vba::{module_types_xls, module_types_xlsx};
+use crate::{
+    CalamineSheet, CalamineTable, DefinedName, DocumentProperties, Error, Picture, SheetMetadata,
+    VbaProject, WorkbookFormatEnum, WorksheetNotFound,
+};
 
 enum SheetsEnum {
     File(Sheets<BufReader<File>>),
-    FileLike(Sheets<Cursor<Vec<u8>>>),
+    FileLike(Sheets<Cursor<Arc<[u8]>>>),
+    Stream(Sheets<PyFileReader>),
+    Mmap(Sheets<Cursor<MmapBytes>>),
+    Buffer(Sheets<Cursor<BufferBytes>>),
     None,
 }
 
-enum WorkbookType {
+#[derive(Clone, Copy)]
+pub enum WorkbookType {
     Xls,
     Xlsx,
     Xlsb,
     Ods,
 }
 
+impl WorkbookType {
+    fn from_format(format: &str) -> PyResult<Self> {
+        match format.to_ascii_lowercase().as_str() {
+            "xls" => Ok(WorkbookType::Xls),
+            "xlsx" | "xlsm" => Ok(WorkbookType::Xlsx),
+            "xlsb" => Ok(WorkbookType::Xlsb),
+            "ods" => Ok(WorkbookType::Ods),
+            _ => Err(PyValueError::new_err(format!(
+                "Unsupported format '{format}', expected one of: xlsx, xlsm, xlsb, xls, ods"
+            ))),
+        }
+    }
+
+    fn from_extension(path: &str) -> Option<Self> {
+        match Path::new(path).extension().and_then(|e| e.to_str()) {
+            Some("xls" | "xla") => Some(WorkbookType::Xls),
+            Some("xlsx" | "xlsm" | "xlam") => Some(WorkbookType::Xlsx),
+            Some("xlsb") => Some(WorkbookType::Xlsb),
+            Some("ods") => Some(WorkbookType::Ods),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            WorkbookType::Xls => "xls",
+            WorkbookType::Xlsx => "xlsx",
+            WorkbookType::Xlsb => "xlsb",
+            WorkbookType::Ods => "ods",
+        }
+    }
+}
+
+impl<RS> From<&Sheets<RS>> for WorkbookType {
+    fn from(sheets: &Sheets<RS>) -> Self {
+        match sheets {
+            Sheets::Xls(_) => WorkbookType::Xls,
+            Sheets::Xlsx(_) => WorkbookType::Xlsx,
+            Sheets::Xlsb(_) => WorkbookType::Xlsb,
+            Sheets::Ods(_) => WorkbookType::Ods,
+        }
+    }
+}
+
 impl From<&SheetsEnum> for WorkbookType {
     fn from(sheets: &SheetsEnum) -> Self {
         match sheets {
-            SheetsEnum::File(f) => match f {
-                Sheets::Xls(_) => WorkbookType::Xls,
-                Sheets::Xlsx(_) => WorkbookType::Xlsx,
-                Sheets::Xlsb(_) => WorkbookType::Xlsb,
-                Sheets::Ods(_) => WorkbookType::Ods,
-            },
-            SheetsEnum::FileLike(f) => match f {
-                Sheets::Xls(_) => WorkbookType::Xls,
-                Sheets::Xlsx(_) => WorkbookType::Xlsx,
-                Sheets::Xlsb(_) => WorkbookType::Xlsb,
-                Sheets::Ods(_) => WorkbookType::Ods,
-            },
+            SheetsEnum::File(f) => WorkbookType::from(f),
+            SheetsEnum::FileLike(f) => WorkbookType::from(f),
+            SheetsEnum::Stream(f) => WorkbookType::from(f),
+            SheetsEnum::Mmap(f) => WorkbookType::from(f),
+            SheetsEnum::Buffer(f) => WorkbookType::from(f),
             SheetsEnum::None => unreachable!(),
         }
     }
 }
 
+impl From<WorkbookType> for WorkbookFormatEnum {
+    fn from(workbook_type: WorkbookType) -> Self {
+        match workbook_type {
+            WorkbookType::Xls => WorkbookFormatEnum::Xls,
+            WorkbookType::Xlsx => WorkbookFormatEnum::Xlsx,
+            WorkbookType::Xlsb => WorkbookFormatEnum::Xlsb,
+            WorkbookType::Ods => WorkbookFormatEnum::Ods,
+        }
+    }
+}
+
+fn open_sheets<RS: Read + Seek>(
+    reader: RS,
+    workbook_type: WorkbookType,
+) -> Result<Sheets<RS>, CalamineCrateError> {
+    match workbook_type {
+        WorkbookType::Xls => Xls::new(reader)
+            .map(Sheets::Xls)
+            .map_err(CalamineCrateError::Xls),
+        WorkbookType::Xlsx => Xlsx::new(reader)
+            .map(Sheets::Xlsx)
+            .map_err(CalamineCrateError::Xlsx),
+        WorkbookType::Xlsb => Xlsb::new(reader)
+            .map(Sheets::Xlsb)
+            .map_err(CalamineCrateError::Xlsb),
+        WorkbookType::Ods => Ods::new(reader)
+            .map(Sheets::Ods)
+            .map_err(CalamineCrateError::Ods),
+    }
+}
+
+/// Opens a workbook with the given reader, or tries every reader in calamine's order
+/// if the type is unknown.
+fn probe_sheets<RS, F>(open: F, workbook_type: Option<WorkbookType>) -> Result<Sheets<RS>, Error>
+where
+    RS: Read + Seek,
+    F: Fn() -> std::io::Result<RS>,
+{
+    let open_reader = || {
+        open()
+            .map_err(CalamineCrateError::Io)
+            .map_err(Error::Calamine)
+    };
+
+    if let Some(workbook_type) = workbook_type {
+        return open_sheets(open_reader()?, workbook_type).map_err(Error::Calamine);
+    }
+
+    let mut errors = Vec::new();
+    for workbook_type in [
+        WorkbookType::Xls,
+        WorkbookType::Xlsx,
+        WorkbookType::Xlsb,
+        WorkbookType::Ods,
+    ] {
+        match open_sheets(open_reader()?, workbook_type) {
+            Ok(sheets) => return Ok(sheets),
+            Err(error) => errors.push((workbook_type.name(), error)),
+        }
+    }
+    Err(Error::FormatNotDetected(errors))
+}
+
+fn worksheet_merge_cells<RS: Read + Seek>(
+    sheets: &mut Sheets<RS>,
+    name: &str,
+) -> Result<Option<Vec<calamine::Dimensions>>, Error> {
+    match sheets {
+        Sheets::Xls(xls_f) => xls_f
+            .merge_cells_by_sheet_name(name)
+            .map(Some)
+            .map_err(CalamineCrateError::Xls)
+            .map_err(Error::Calamine),
+        Sheets::Xlsx(xlsx_f) => xlsx_f
+            .merge_cells_by_sheet_name(name)
+            .map(Some)
+            .map_err(CalamineCrateError::Xlsx)
+            .map_err(Error::Calamine),
+        _ => Ok(None),
+    }
+}
+
+/// Reads `nrows` rows after `skiprows` rows of a sheet, counted from the header row or
+/// the first non-empty row, and returns them with the absolute rows they span, `None`
+/// without a header row and cells. XLSX and XLSB sheets are only parsed up to the last
+/// row and span the columns of the sheet dimension. XLS and ODS have no cell reader and
+/// are read as a whole.
+fn worksheet_range_rows<RS: Read + Seek>(
+    sheets: &mut Sheets<RS>,
+    name: &str,
+    header_row: Option<u32>,
+    skiprows: u32,
+    nrows: Option<u32>,
+) -> Result<(Range<Data>, Option<std::ops::Range<u32>>), Error> {
+    match sheets {
+        Sheets::Xlsx(xlsx_f) => {
+            let mut reader = match xlsx_f.worksheet_cells_reader(name) {
+                Ok(reader) => reader,
+                Err(XlsxError::NotAWorksheet(_)) => return Ok((Range::default(), None)),
+                Err(e) => return Err(Error::Calamine(CalamineCrateError::Xlsx(e))),
+            };
+            let columns = dimension_columns(reader.dimensions());
+            read_rows(|| reader.next_cell(), columns, header_row, skiprows, nrows)
+                .map_err(CalamineCrateError::Xlsx)
+                .map_err(Error::Calamine)
+        }
+        Sheets::Xlsb(xlsb_f) => {
+            let mut reader = xlsb_f
+                .worksheet_cells_reader(name)
+                .map_err(CalamineCrateError::Xlsb)
+                .map_err(Error::Calamine)?;
+            let columns = dimension_columns(reader.dimensions());
+            read_rows(|| reader.next_cell(), columns, header_row, skiprows, nrows)
+                .map_err(CalamineCrateError::Xlsb)
+                .map_err(Error::Calamine)
+        }
+        _ => {
+            let range = sheets.worksheet_range(name).map_err(Error::Calamine)?;
+            let columns = range.start().zip(range.end()).map(|(s, e)| (s.1, e.1));
+            let start = range.start().unwrap_or_default();
+            let mut cells = range.used_cells().map(|(row, col, value)| {
+                Ok(Some(Cell::new(
+                    (start.0 + row as u32, start.1 + col as u32),
+                    value.clone(),
+                )))
+            });
+            read_rows(
+                || cells.next().unwrap_or(Ok(None)),
+                columns,
+                header_row,
+                skiprows,
+                nrows,
+            )
+        }
+    }
+}
+
+/// First and last column of a sheet dimension, `None` without one as `A1` alone is written
+/// for an empty sheet.
+fn dimension_columns(dimensions: calamine::Dimensions) -> Option<(u32, u32)> {
+    (dimensions.end != (0, 0)).then_some((dimensions.start.1, dimensions.end.1))
+}
+
+/// Collects the non-empty cells of the rows selected as in `worksheet_range_rows`, the cells
+/// are read in row order and reading stops at the first cell after the last row. The range
+/// is widened to `columns`, the columns of the whole sheet, so that it doesn't depend on
+/// the rows read.
+fn read_rows<T, E>(
+    mut next_cell: impl FnMut() -> Result<Option<Cell<T>>, E>,
+    columns: Option<(u32, u32)>,
+    header_row: Option<u32>,
+    skiprows: u32,
+    nrows: Option<u32>,
+) -> Result<(Range<Data>, Option<std::ops::Range<u32>>), E>
+where
+    T: CellType + DataType + Into<Data>,
+{
+    let span = |first_row: u32| {
+        let first_row = first_row.saturating_add(skiprows);
+        first_row..nrows.map_or(u32::MAX, |nrows| first_row.saturating_add(nrows))
+    };
+    let mut rows = header_row.map(span);
+    let mut cells = Vec::new();
+    while let Some(cell) = next_cell()? {
+        let (row, col) = cell.get_position();
+        if rows.as_ref().is_some_and(|rows| row >= rows.end) {
+            break;
+        }
+        if cell.get_value().is_empty() {
+            continue;
+        }
+        // without a header row, rows are counted from the first non-empty one
+        let rows = rows.get_or_insert_with(|| span(row));
+        if rows.contains(&row) {
+            cells.push(Cell::new((row, col), cell.get_value().clone().into()));
+        }
+    }
+    let range = Range::from_sparse(cells);
+    let range = match (columns, range.start(), range.end()) {
+        (Some((first, last)), Some(start), Some(end)) if first < start.1 || last > end.1 => {
+            range.range((start.0, first.min(start.1)), (end.0, last.max(end.1)))
+        }
+        _ => range,
+    };
+    Ok((range, rows))
+}
+
+fn has_1904_epoch<RS: Read + Seek>(sheets: &Sheets<RS>) -> Option<bool> {
+    match sheets {
+        Sheets::Xls(xls_f) => Some(xls_f.has_1904_epoch()),
+        Sheets::Xlsx(xlsx_f) => Some(xlsx_f.has_1904_epoch()),
+        Sheets::Xlsb(xlsb_f) => Some(xlsb_f.has_1904_epoch()),
+        // ODS stores dates as is
+        Sheets::Ods(_) => None,
+    }
+}
+
+fn pictures<RS: Read + Seek>(sheets: &Sheets<RS>) -> Vec<Picture> {
+    // calamine only reads the sheet and anchor of XLSX pictures
+    let pictures = sheets.pictures_with_metadata();
+    if !pictures.is_empty() {
+        return pictures.into_iter().map(Picture::from).collect();
+    }
+    sheets
+        .pictures()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(extension, data)| Picture::new(extension, data))
+        .collect()
+}
+
+fn load_tables<RS: Read + Seek>(sheets: &mut Sheets<RS>) -> Result<(), Error> {
+    match sheets {
+        Sheets::Xlsx(xlsx_f) => xlsx_f
+            .load_tables()
+            .map_err(CalamineCrateError::Xlsx)
+            .map_err(Error::Calamine),
+        _ => Err(Error::TablesNotSupported),
+    }
+}
+
+fn table_names<RS: Read + Seek>(sheets: &Sheets<RS>) -> Result<Vec<String>, Error> {
+    match sheets {
+        Sheets::Xlsx(xlsx_f) => Ok(xlsx_f
+            .table_names()
+            .iter()
+            .map(|s| s.to_owned().to_owned())
+            .collect()),
+        _ => Err(Error::TablesNotSupported),
+    }
+}
+
+fn get_table_by_name<RS: Read + Seek>(
+    sheets: &mut Sheets<RS>,
+    name: &str,
+) -> Result<CalamineTable, Error> {
+    match sheets {
+        Sheets::Xlsx(xlsx_f) => xlsx_f
+            .table_by_name(name)
+            .map_err(CalamineCrateError::Xlsx)
+            .map_err(Error::Calamine)
+            .map(|t| {
+                CalamineTable::new(
+                    t.name().to_owned(),
+                    t.sheet_name().to_owned(),
+                    t.columns().iter().map(|s| s.to_owned()).collect(),
+                    t.data().to_owned(),
+                )
+            }),
+        _ => Err(Error::TablesNotSupported),
+    }
+}
+
 impl SheetsEnum {
     fn sheets_metadata(&self) -> Vec<SheetMetadata> {
         match self {
             SheetsEnum::File(f) => f.sheets_metadata(),
             SheetsEnum::FileLike(f) => f.sheets_metadata(),
+            SheetsEnum::Stream(f) => f.sheets_metadata(),
+            SheetsEnum::Mmap(f) => f.sheets_metadata(),
+            SheetsEnum::Buffer(f) => f.sheets_metadata(),
             SheetsEnum::None => unreachable!(),
         }
         .iter()
@@ -61,6 +375,32 @@ impl SheetsEnum {
         match self {
             SheetsEnum::File(f) => f.sheet_names(),
             SheetsEnum::FileLike(f) => f.sheet_names(),
+            SheetsEnum::Stream(f) => f.sheet_names(),
+            SheetsEnum::Mmap(f) => f.sheet_names(),
+            SheetsEnum::Buffer(f) => f.sheet_names(),
+            SheetsEnum::None => unreachable!(),
+        }
+    }
+
+    fn defined_names(&self) -> Vec<(String, String)> {
+        match self {
+            SheetsEnum::File(f) => f.defined_names(),
+            SheetsEnum::FileLike(f) => f.defined_names(),
+            SheetsEnum::Stream(f) => f.defined_names(),
+            SheetsEnum::Mmap(f) => f.defined_names(),
+            SheetsEnum::Buffer(f) => f.defined_names(),
+            SheetsEnum::None => unreachable!(),
+        }
+        .to_vec()
+    }
+
+    fn has_1904_epoch(&self) -> Option<bool> {
+        match self {
+            SheetsEnum::File(f) => has_1904_epoch(f),
+            SheetsEnum::FileLike(f) => has_1904_epoch(f),
+            SheetsEnum::Stream(f) => has_1904_epoch(f),
+            SheetsEnum::Mmap(f) => has_1904_epoch(f),
+            SheetsEnum::Buffer(f) => has_1904_epoch(f),
             SheetsEnum::None => unreachable!(),
         }
     }
@@ -69,6 +409,76 @@ impl SheetsEnum {
         match self {
             SheetsEnum::File(f) => f.worksheet_range(name).map_err(Error::Calamine),
             SheetsEnum::FileLike(f) => f.worksheet_range(name).map_err(Error::Calamine),
+            SheetsEnum::Stream(f) => f.worksheet_range(name).map_err(Error::Calamine),
+            SheetsEnum::Mmap(f) => f.worksheet_range(name).map_err(Error::Calamine),
+            SheetsEnum::Buffer(f) => f.worksheet_range(name).map_err(Error::Calamine),
+            SheetsEnum::None => Err(Error::WorkbookClosed),
+        }
+    }
+
+    fn with_header_row(&mut self, header_row: HeaderRow) {
+        match self {
+            SheetsEnum::File(f) => _ = f.with_header_row(header_row),
+            SheetsEnum::FileLike(f) => _ = f.with_header_row(header_row),
+            SheetsEnum::Stream(f) => _ = f.with_header_row(header_row),
+            SheetsEnum::Mmap(f) => _ = f.with_header_row(header_row),
+            SheetsEnum::Buffer(f) => _ = f.with_header_row(header_row),
+            SheetsEnum::None => {}
+        }
+    }
+
+    fn worksheet_range_rows(
+        &mut self,
+        name: &str,
+        header_row: Option<u32>,
+        skiprows: u32,
+        nrows: Option<u32>,
+    ) -> Result<
+        (
+            calamine::Range<calamine::Data>,
+            Option<std::ops::Range<u32>>,
+        ),
+        Error,
+    > {
+        match self {
+            SheetsEnum::File(f) => worksheet_range_rows(f, name, header_row, skiprows, nrows),
+            SheetsEnum::FileLike(f) => worksheet_range_rows(f, name, header_row, skiprows, nrows),
+            SheetsEnum::Stream(f) => worksheet_range_rows(f, name, header_row, skiprows, nrows),
+            SheetsEnum::Mmap(f) => worksheet_range_rows(f, name, header_row, skiprows, nrows),
+            SheetsEnum::Buffer(f) => worksheet_range_rows(f, name, header_row, skiprows, nrows),
+            SheetsEnum::None => Err(Error::WorkbookClosed),
+        }
+    }
+
+    fn worksheet_formula(&mut self, name: &str) -> Result<calamine::Range<String>, Error> {
+        match self {
+            SheetsEnum::File(f) => f.worksheet_formula(name).map_err(Error::Calamine),
+            SheetsEnum::FileLike(f) => f.worksheet_formula(name).map_err(Error::Calamine),
+            SheetsEnum::Stream(f) => f.worksheet_formula(name).map_err(Error::Calamine),
+            SheetsEnum::Mmap(f) => f.worksheet_formula(name).map_err(Error::Calamine),
+            SheetsEnum::Buffer(f) => f.worksheet_formula(name).map_err(Error::Calamine),
+            SheetsEnum::None => Err(Error::WorkbookClosed),
+        }
+    }
+
+    fn vba_project(&mut self) -> Result<Option<calamine::vba::VbaProject>, Error> {
+        match self {
+            SheetsEnum::File(f) => f.vba_project().map_err(Error::Calamine),
+            SheetsEnum::FileLike(f) => f.vba_project().map_err(Error::Calamine),
+            SheetsEnum::Stream(f) => f.vba_project().map_err(Error::Calamine),
+            SheetsEnum::Mmap(f) => f.vba_project().map_err(Error::Calamine),
+            SheetsEnum::Buffer(f) => f.vba_project().map_err(Error::Calamine),
+            SheetsEnum::None => Err(Error::WorkbookClosed),
+        }
+    }
+
+    fn pictures(&self) -> Result<Vec<Picture>, Error> {
+        match self {
+            SheetsEnum::File(f) => Ok(pictures(f)),
+            SheetsEnum::FileLike(f) => Ok(pictures(f)),
+            SheetsEnum::Stream(f) => Ok(pictures(f)),
+            SheetsEnum::Mmap(f) => Ok(pictures(f)),
+            SheetsEnum::Buffer(f) => Ok(pictures(f)),
             SheetsEnum::None => Err(Error::WorkbookClosed),
         }
     }
@@ -78,107 +488,44 @@ impl SheetsEnum {
         name: &str,
     ) -> Result<Option<Vec<calamine::Dimensions>>, Error> {
         match self {
-            SheetsEnum::File(f) => match f {
-                Sheets::Xls(xls_f) => xls_f
-                    .merge_cells_by_sheet_name(name)
-                    .map(Some)
-                    .map_err(CalamineCrateError::Xls)
-                    .map_err(Error::Calamine),
-                Sheets::Xlsx(xlsx_f) => xlsx_f
-                    .merge_cells_by_sheet_name(name)
-                    .map(Some)
-                    .map_err(CalamineCrateError::Xlsx)
-                    .map_err(Error::Calamine),
-                _ => Ok(None),
-            },
-            SheetsEnum::FileLike(f) => match f {
-                Sheets::Xls(xls_f) => xls_f
-                    .merge_cells_by_sheet_name(name)
-                    .map(Some)
-                    .map_err(CalamineCrateError::Xls)
-                    .map_err(Error::Calamine),
-                Sheets::Xlsx(xlsx_f) => xlsx_f
-                    .merge_cells_by_sheet_name(name)
-                    .map(Some)
-                    .map_err(CalamineCrateError::Xlsx)
-                    .map_err(Error::Calamine),
-                _ => Ok(None),
-            },
+            SheetsEnum::File(f) => worksheet_merge_cells(f, name),
+            SheetsEnum::FileLike(f) => worksheet_merge_cells(f, name),
+            SheetsEnum::Stream(f) => worksheet_merge_cells(f, name),
+            SheetsEnum::Mmap(f) => worksheet_merge_cells(f, name),
+            SheetsEnum::Buffer(f) => worksheet_merge_cells(f, name),
             SheetsEnum::None => Err(Error::WorkbookClosed),
         }
     }
 
     fn load_tables(&mut self) -> Result<(), Error> {
         match self {
-            SheetsEnum::File(f) => match f {
-                Sheets::Xlsx(xlsx_f) => xlsx_f
-                    .load_tables()
-                    .map_err(CalamineCrateError::Xlsx)
-                    .map_err(Error::Calamine),
-                _ => Err(Error::TablesNotSupported),
-            },
-            SheetsEnum::FileLike(f) => match f {
-                Sheets::Xlsx(xlsx_f) => xlsx_f
-                    .load_tables()
-                    .map_err(CalamineCrateError::Xlsx)
-                    .map_err(Error::Calamine),
-                _ => Err(Error::TablesNotSupported),
-            },
+            SheetsEnum::File(f) => load_tables(f),
+            SheetsEnum::FileLike(f) => load_tables(f),
+            SheetsEnum::Stream(f) => load_tables(f),
+            SheetsEnum::Mmap(f) => load_tables(f),
+            SheetsEnum::Buffer(f) => load_tables(f),
             SheetsEnum::None => Err(Error::WorkbookClosed),
         }
     }
 
     fn table_names(&self) -> Result<Vec<String>, Error> {
         match self {
-            SheetsEnum::File(f) => match f {
-                Sheets::Xlsx(xlsx_f) => Ok(xlsx_f.table_names()),
-                _ => Err(Error::TablesNotSupported),
-            },
-            SheetsEnum::FileLike(f) => match f {
-                Sheets::Xlsx(xlsx_f) => Ok(xlsx_f.table_names()),
-                _ => Err(Error::TablesNotSupported),
-            },
+            SheetsEnum::File(f) => table_names(f),
+            SheetsEnum::FileLike(f) => table_names(f),
+            SheetsEnum::Stream(f) => table_names(f),
+            SheetsEnum::Mmap(f) => table_names(f),
+            SheetsEnum::Buffer(f) => table_names(f),
             SheetsEnum::None => Err(Error::WorkbookClosed),
         }
-        .map(|v| {
-            v.iter()
-                .map(|s| s.to_owned().to_owned())
-                .collect::<Vec<String>>()
-        })
     }
 
     fn get_table_by_name(&mut self, name: &str) -> Result<CalamineTable, Error> {
         match self {
-            SheetsEnum::File(f) => match f {
-                Sheets::Xlsx(xlsx_f) => xlsx_f
-                    .table_by_name(name)
-                    .map_err(CalamineCrateError::Xlsx)
-                    .map_err(Error::Calamine)
-                    .map(|t| {
-                        CalamineTable::new(
-                            t.name().to_owned(),
-                            t.sheet_name().to_owned(),
-                            t.columns().iter().map(|s| s.to_owned()).collect(),
-                            t.data().to_owned(),
-                        )
-                    }),
-                _ => Err(Error::TablesNotSupported),
-            },
-            SheetsEnum::FileLike(f) => match f {
-                Sheets::Xlsx(xlsx_f) => xlsx_f
-                    .table_by_name(name)
-                    .map_err(CalamineCrateError::Xlsx)
-                    .map_err(Error::Calamine)
-                    .map(|t| {
-                        CalamineTable::new(
-                            t.name().to_owned(),
-                            t.sheet_name().to_owned(),
-                            t.columns().iter().map(|s| s.to_owned()).collect(),
-                            t.data().to_owned(),
-                        )
-                    }),
-                _ => Err(Error::TablesNotSupported),
-            },
+            SheetsEnum::File(f) => get_table_by_name(f, name),
+            SheetsEnum::FileLike(f) => get_table_by_name(f, name),
+            SheetsEnum::Stream(f) => get_table_by_name(f, name),
+            SheetsEnum::Mmap(f) => get_table_by_name(f, name),
+            SheetsEnum::Buffer(f) => get_table_by_name(f, name),
             SheetsEnum::None => Err(Error::WorkbookClosed),
         }
     }
@@ -189,11 +536,16 @@ pub struct CalamineWorkbook {
     #[pyo3(get)]
     path: Option<String>,
     workbook_type: WorkbookType,
+    source: WorkbookSource,
+    info: OnceLock<WorkbookInfo>,
+    date1904: Option<bool>,
     sheets: SheetsEnum,
     #[pyo3(get)]
     sheets_metadata: Vec<SheetMetadata>,
     #[pyo3(get)]
     sheet_names: Vec<String>,
+    /// Name and formula of the defined names
+    defined_names: Vec<(String, String)>,
     table_names: Option<Vec<String>>,
 }
 
@@ -207,51 +559,130 @@ impl CalamineWorkbook {
     }
 
     #[classmethod]
-    #[pyo3(name = "from_object", signature = (path_or_filelike, load_tables=false))]
+    #[pyo3(
+        name = "from_object",
+        signature = (path_or_filelike, load_tables=false, mmap=false, format=None, password=None)
+    )]
     fn py_from_object(
         _cls: &Bound<'_, PyType>,
         py: Python<'_>,
         path_or_filelike: Py<PyAny>,
         load_tables: bool,
+        mmap: bool,
+        format: Option<&str>,
+        password: Option<&str>,
     ) -> PyResult<Self> {
-        Self::from_object(py, path_or_filelike, load_tables)
+        Self::from_object(py, path_or_filelike, load_tables, mmap, format, password)
     }
 
     #[classmethod]
-    #[pyo3(name = "from_filelike", signature = (filelike, load_tables=false))]
+    #[pyo3(
+        name = "from_filelike",
+        signature = (filelike, load_tables=false, format=None, password=None)
+    )]
     fn py_from_filelike(
         _cls: &Bound<'_, PyType>,
         py: Python<'_>,
         filelike: Py<PyAny>,
         load_tables: bool,
+        format: Option<&str>,
+        password: Option<&str>,
+    ) -> PyResult<Self> {
+        let workbook_type = format.map(WorkbookType::from_format).transpose()?;
+        py.detach(|| Self::from_filelike(filelike, load_tables, workbook_type, password))
+    }
+
+    #[classmethod]
+    #[pyo3(
+        name = "from_buffer",
+        signature = (buffer, load_tables=false, format=None, password=None)
+    )]
+    fn py_from_buffer(
+        _cls: &Bound<'_, PyType>,
+        py: Python<'_>,
+        buffer: PyBuffer<u8>,
+        load_tables: bool,
+        format: Option<&str>,
+        password: Option<&str>,
     ) -> PyResult<Self> {
-        py.detach(|| Self::from_filelike(filelike, load_tables))
+        let workbook_type = format.map(WorkbookType::from_format).transpose()?;
+        Self::from_buffer(py, buffer, load_tables, workbook_type, password)
     }
 
     #[classmethod]
-    #[pyo3(name = "from_path", signature = (path, load_tables=false))]
+    #[pyo3(
+        name = "from_path",
+        signature = (path, load_tables=false, mmap=false, format=None, password=None)
+    )]
     fn py_from_path(
         _cls: &Bound<'_, PyType>,
         py: Python<'_>,
         path: Py<PyAny>,
         load_tables: bool,
+        mmap: bool,
+        format: Option<&str>,
+        password: Option<&str>,
     ) -> PyResult<Self> {
+        let workbook_type = format.map(WorkbookType::from_format).transpose()?;
         if let Ok(string_ref) = path.extract::<PathBuf>(py) {
             let path = string_ref.to_string_lossy().to_string();
-            return py.detach(|| Self::from_path(&path, load_tables));
+            return py
+                .detach(|| Self::from_path(&path, load_tables, mmap, workbook_type, password));
         }
 
         Err(PyTypeError::new_err(""))
     }
 
-    #[pyo3(name = "get_sheet_by_name")]
-    fn py_get_sheet_by_name(&mut self, py: Python<'_>, name: &str) -> PyResult<CalamineSheet> {
-        py.detach(|| self.get_sheet_by_name(name))
+    #[pyo3(
+        name = "get_sheet_by_name",
+        signature = (name, nrows=None, skiprows=0, header_row=None)
+    )]
+    fn py_get_sheet_by_name(
+        &mut self,
+        py: Python<'_>,
+        name: &str,
+        nrows: Option<u32>,
+        skiprows: u32,
+        header_row: Option<u32>,
+    ) -> PyResult<CalamineSheet> {
+        py.detach(|| self.get_sheet_by_name(name, nrows, skiprows, header_row))
+    }
+
+    #[pyo3(
+        name = "get_sheet_by_index",
+        signature = (index, nrows=None, skiprows=0, header_row=None)
+    )]
+    fn py_get_sheet_by_index(
+        &mut self,
+        py: Python<'_>,
+        index: usize,
+        nrows: Option<u32>,
+        skiprows: u32,
+        header_row: Option<u32>,
+    ) -> PyResult<CalamineSheet> {
+        py.detach(|| self.get_sheet_by_index(index, nrows, skiprows, header_row))
     }
 
-    #[pyo3(name = "get_sheet_by_index")]
-    fn py_get_sheet_by_index(&mut self, py: Python<'_>, index: usize) -> PyResult<CalamineSheet> {
-        py.detach(|| self.get_sheet_by_index(index))
+    #[pyo3(name = "get_sheet_formulas", signature = (name, skip_empty_area=true))]
+    fn py_get_sheet_formulas(
+        &mut self,
+        py: Python<'_>,
+        name: &str,
+        skip_empty_area: bool,
+    ) -> PyResult<Vec<Vec<String>>> {
+        py.detach(|| self.get_sheet_formulas(name, skip_empty_area))
+    }
+
+    #[pyo3(name = "iter_sheet_rows", signature = (name, usecols=None, empty_value=EmptyValue::default()))]
+    fn py_iter_sheet_rows(
+        &mut self,
+        py: Python<'_>,
+        name: &str,
+        usecols: Option<&Bound<'_, PyAny>>,
+        empty_value: EmptyValue,
+    ) -> PyResult<CalamineRowIterator> {
+        let usecols = usecols.map(UseCols::extract).transpose()?;
+        py.detach(|| self.iter_sheet_rows(name, usecols, empty_value))
     }
 
     #[getter]
@@ -265,6 +696,109 @@ impl CalamineWorkbook {
         }
     }
 
+    #[getter]
+    fn defined_names(&self, py: Python<'_>) -> PyResult<Vec<DefinedName>> {
+        // calamine may skip names, so scopes are matched by name, in order for duplicates
+        let mut scopes: Vec<_> = self.info(py)?.defined_name_scopes.iter().collect();
+        Ok(self
+            .defined_names
+            .iter()
+            .map(|(name, formula)| {
+                let scope = scopes
+                    .iter()
+                    .position(|(n, _)| n == name)
+                    .and_then(|i| scopes.remove(i).1)
+                    .and_then(|index| self.sheets_metadata.get(index))
+                    .map(|sheet| sheet.name().to_string());
+                DefinedName::new(name.clone(), scope, formula.clone())
+            })
+            .collect())
+    }
+
+    #[pyo3(name = "get_range_by_name")]
+    fn py_get_range_by_name(&mut self, py: Python<'_>, name: &str) -> PyResult<CalamineSheet> {
+        // a workbook-level name takes precedence over sheet-level ones
+        let defined_name = self
+            .defined_names(py)?
+            .into_iter()
+            .filter(|n| n.name() == name)
+            .min_by_key(|n| n.scope().is_some())
+            .ok_or_else(|| Error::DefinedNameNotFound(name.to_string()))?;
+        let reference = CellReference::parse(defined_name.formula()).ok_or_else(|| {
+            Error::UnsupportedReference(name.to_string(), defined_name.formula().to_string())
+        })?;
+        py.detach(|| self.get_range(&reference))
+    }
+
+    #[getter]
+    fn vba_project(&mut self, py: Python<'_>) -> PyResult<Option<VbaProject>> {
+        py.detach(|| {
+            let Some(project) = self.sheets.vba_project()? else {
+                return Ok(None);
+            };
+            // calamine doesn't expose module types, they are left unknown if unreadable
+            let reader = self.source.open()?;
+            let module_types = match self.workbook_type {
+                WorkbookType::Xls => module_types_xls(reader),
+                WorkbookType::Xlsx | WorkbookType::Xlsb => module_types_xlsx(reader),
+                WorkbookType::Ods => Ok(Default::default()),
+            }
+            .unwrap_or_default();
+            Ok(Some(VbaProject::new(&project, &module_types)?))
+        })
+    }
+
+    #[getter]
+    fn pictures(&self, py: Python<'_>) -> PyResult<Vec<Picture>> {
+        py.detach(|| Ok(self.sheets.pictures()?))
+    }
+
+    #[getter]
+    fn document_properties(&self, py: Python<'_>) -> PyResult<DocumentProperties> {
+        py.detach(|| {
+            let reader = self.source.open()?;
+            Ok(match self.workbook_type {
+                WorkbookType::Xls => DocumentProperties::xls(reader),
+                WorkbookType::Xlsx | WorkbookType::Xlsb => DocumentProperties::xlsx(reader),
+                WorkbookType::Ods => DocumentProperties::ods(reader),
+            }?)
+        })
+    }
+
+    #[getter]
+    fn custom_properties<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let properties = py.detach(|| {
+            let reader = self.source.open()?;
+            match self.workbook_type {
+                WorkbookType::Xls => custom_properties_xls(reader),
+                WorkbookType::Xlsx | WorkbookType::Xlsb => custom_properties_xlsx(reader),
+                WorkbookType::Ods => custom_properties_ods(reader),
+            }
+        })?;
+        properties.into_py_dict(py)
+    }
+
+    #[getter]
+    fn format(&self) -> WorkbookFormatEnum {
+        self.workbook_type.into()
+    }
+
+    #[getter]
+    fn macros_enabled(&self, py: Python<'_>) -> PyResult<bool> {
+        Ok(self.info(py)?.macros_enabled)
+    }
+
+    #[getter]
+    fn biff_version(&self, py: Python<'_>) -> PyResult<Option<u8>> {
+        Ok(self.info(py)?.biff_version)
+    }
+
+    #[getter]
+    fn date_system(&self) -> Option<u16> {
+        self.date1904
+            .map(|date1904| if date1904 { 1904 } else { 1900 })
+    }
+
     #[pyo3(name = "get_table_by_name")]
     fn py_get_table_by_name(&mut self, py: Python<'_>, name: &str) -> PyResult<CalamineTable> {
         py.detach(|| self.get_table_by_name(name))
@@ -299,45 +833,165 @@ impl CalamineWorkbook {
         py: Python<'_>,
         path_or_filelike: Py<PyAny>,
         load_tables: bool,
+        mmap: bool,
+        format: Option<&str>,
+        password: Option<&str>,
     ) -> PyResult<Self> {
+        let workbook_type = format.map(WorkbookType::from_format).transpose()?;
+
         if let Ok(string_ref) = path_or_filelike.extract::<PathBuf>(py) {
             let path = string_ref.to_string_lossy().to_string();
-            return py.detach(|| Self::from_path(&path, load_tables));
+            return py
+                .detach(|| Self::from_path(&path, load_tables, mmap, workbook_type, password));
+        }
+
+        if mmap {
+            return Err(PyValueError::new_err("mmap is supported for paths only"));
         }
 
-        py.detach(|| Self::from_filelike(path_or_filelike, load_tables))
+        if let Ok(buffer) = PyBuffer::<u8>::get(path_or_filelike.bind(py)) {
+            return Self::from_buffer(py, buffer, load_tables, workbook_type, password);
+        }
+
+        py.detach(|| Self::from_filelike(path_or_filelike, load_tables, workbook_type, password))
     }
 
-    pub fn from_filelike(filelike: Py<PyAny>, load_tables: bool) -> PyResult<Self> {
-        let mut buf = vec![];
-        PyFileLikeObject::with_requirements(filelike, true, false, true, false)?
-            .read_to_end(&mut buf)?;
-        let reader = Cursor::new(buf);
-        let mut sheets =
-            SheetsEnum::FileLike(open_workbook_auto_from_rs(reader).map_err(Error::Calamine)?);
-        let sheet_names = sheets.sheet_names().to_owned();
-        let sheets_metadata = sheets.sheets_metadata().to_owned();
+    pub fn from_buffer(
+        py: Python<'_>,
+        buffer: PyBuffer<u8>,
+        load_tables: bool,
+        workbook_type: Option<WorkbookType>,
+        password: Option<&str>,
+    ) -> PyResult<Self> {
+        match BufferBytes::new(buffer) {
+            Ok(bytes) => py.detach(|| {
+                if let Some(password) = password {
+                    if let Some(buf) = decrypt(Cursor::new(bytes.clone()), password)? {
+                        return Self::from_decrypted(None, buf, load_tables, workbook_type);
+                    }
+                }
+                let sheets = SheetsEnum::Buffer(probe_sheets(
+                    || Ok(Cursor::new(bytes.clone())),
+                    workbook_type,
+                )?);
+                Self::from_sheets(None, WorkbookSource::Buffer(bytes), sheets, load_tables)
+            }),
+            // Non-contiguous buffers (strided memoryview, ...) can't be borrowed as a slice
+            Err(buffer) => {
+                let buf: Arc<[u8]> = buffer.to_vec(py)?.into();
+                py.detach(|| {
+                    if let Some(password) = password {
+                        if let Some(buf) = decrypt(Cursor::new(buf.clone()), password)? {
+                            return Self::from_decrypted(None, buf, load_tables, workbook_type);
+                        }
+                    }
+                    let sheets = SheetsEnum::FileLike(probe_sheets(
+                        || Ok(Cursor::new(buf.clone())),
+                        workbook_type,
+                    )?);
+                    Self::from_sheets(None, WorkbookSource::Bytes(buf), sheets, load_tables)
+                })
+            }
+        }
+    }
 
-        let mut table_names: Option<Vec<String>> = None;
-        if load_tables {
-            sheets.load_tables()?;
-            table_names = Some(sheets.table_names()?);
+    pub fn from_filelike(
+        filelike: Py<PyAny>,
+        load_tables: bool,
+        workbook_type: Option<WorkbookType>,
+        password: Option<&str>,
+    ) -> PyResult<Self> {
+        let seekable = Python::attach(|py| is_seekable(filelike.bind(py)));
+        let (source, sheets) = if seekable {
+            let reader = PyFileReader::new(PyFileLikeObject::with_requirements(
+                filelike, true, false, true, false,
+            )?)?;
+            if let Some(password) = password {
+                if let Some(buf) = decrypt(reader.clone(), password)? {
+                    return Self::from_decrypted(None, buf, load_tables, workbook_type);
+                }
+            }
+            let sheets = SheetsEnum::Stream(probe_sheets(|| Ok(reader.clone()), workbook_type)?);
+            (WorkbookSource::Stream(reader), sheets)
+        } else {
+            // Non-seekable streams (pipes, sockets, ...) have to be buffered
+            let mut buf = vec![];
+            PyFileLikeObject::with_requirements(filelike, true, false, false, false)?
+                .read_to_end(&mut buf)?;
+            let buf: Arc<[u8]> = buf.into();
+            if let Some(password) = password {
+                if let Some(buf) = decrypt(Cursor::new(buf.clone()), password)? {
+                    return Self::from_decrypted(None, buf, load_tables, workbook_type);
+                }
+            }
+            let sheets = SheetsEnum::FileLike(probe_sheets(
+                || Ok(Cursor::new(buf.clone())),
+                workbook_type,
+            )?);
+            (WorkbookSource::Bytes(buf), sheets)
+        };
+        Self::from_sheets(None, source, sheets, load_tables)
+    }
+
+    pub fn from_path(
+        path: &str,
+        load_tables: bool,
+        mmap: bool,
+        workbook_type: Option<WorkbookType>,
+        password: Option<&str>,
+    ) -> PyResult<Self> {
+        let workbook_type = workbook_type.or_else(|| WorkbookType::from_extension(path));
+        if let Some(password) = password {
+            if let Some(buf) = decrypt(File::open(path).map(BufReader::new)?, password)? {
+                return Self::from_decrypted(
+                    Some(path.to_string()),
+                    buf,
+                    load_tables,
+                    workbook_type,
+                );
+            }
         }
+        let (source, sheets) = if mmap {
+            let bytes = MmapBytes::open(path)?;
+            let sheets = SheetsEnum::Mmap(probe_sheets(
+                || Ok(Cursor::new(bytes.clone())),
+                workbook_type,
+            )?);
+            (WorkbookSource::Mmap(bytes), sheets)
+        } else {
+            let sheets = SheetsEnum::File(probe_sheets(
+                || File::open(path).map(BufReader::new),
+                workbook_type,
+            )?);
+            (WorkbookSource::Path(path.to_string()), sheets)
+        };
+        Self::from_sheets(Some(path.to_string()), source, sheets, load_tables)
+    }
 
-        Ok(Self {
-            path: None,
-            workbook_type: WorkbookType::from(&sheets),
-            sheets,
-            sheets_metadata,
-            sheet_names,
-            table_names,
-        })
+    /// Opens a decrypted workbook, which is kept in memory.
+    fn from_decrypted(
+        path: Option<String>,
+        buf: Vec<u8>,
+        load_tables: bool,
+        workbook_type: Option<WorkbookType>,
+    ) -> PyResult<Self> {
+        let buf: Arc<[u8]> = buf.into();
+        let sheets = SheetsEnum::FileLike(probe_sheets(
+            || Ok(Cursor::new(buf.clone())),
+            workbook_type,
+        )?);
+        Self::from_sheets(path, WorkbookSource::Bytes(buf), sheets, load_tables)
     }
 
-    pub fn from_path(path: &str, load_tables: bool) -> PyResult<Self> {
-        let mut sheets = SheetsEnum::File(open_workbook_auto(path).map_err(Error::Calamine)?);
+    fn from_sheets(
+        path: Option<String>,
+        source: WorkbookSource,
+        mut sheets: SheetsEnum,
+        load_tables: bool,
+    ) -> PyResult<Self> {
         let sheet_names = sheets.sheet_names().to_owned();
         let sheets_metadata = sheets.sheets_metadata().to_owned();
+        let defined_names = sheets.defined_names();
 
         let mut table_names: Option<Vec<String>> = None;
         if load_tables {
@@ -345,18 +999,103 @@ impl CalamineWorkbook {
             table_names = Some(sheets.table_names()?);
         }
         Ok(Self {
-            path: Some(path.to_string()),
+            path,
             workbook_type: WorkbookType::from(&sheets),
+            source,
+            info: OnceLock::new(),
+            date1904: sheets.has_1904_epoch(),
             sheets,
             sheets_metadata,
             sheet_names,
+            defined_names,
             table_names,
         })
     }
 
-    fn get_sheet_by_name(&mut self, name: &str) -> PyResult<CalamineSheet> {
-        let range = self.sheets.worksheet_range(name)?;
-        let merge_cells_range = self.sheets.worksheet_merge_cells(name)?;
+    /// Reads the details calamine doesn't expose once, on first access.
+    fn info(&self, py: Python<'_>) -> PyResult<&WorkbookInfo> {
+        if let Some(info) = self.info.get() {
+            return Ok(info);
+        }
+        let info = py.detach(|| {
+            let reader = self.source.open()?;
+            match self.workbook_type {
+                WorkbookType::Xls => WorkbookInfo::xls(reader),
+                WorkbookType::Xlsx | WorkbookType::Xlsb => WorkbookInfo::xlsx(reader),
+                WorkbookType::Ods => WorkbookInfo::ods(reader),
+            }
+        })?;
+        Ok(self.info.get_or_init(|| info))
+    }
+
+    fn get_sheet_by_name(
+        &mut self,
+        name: &str,
+        nrows: Option<u32>,
+        skiprows: u32,
+        header_row: Option<u32>,
+    ) -> PyResult<CalamineSheet> {
+        if nrows.is_none() && skiprows == 0 {
+            let range = match (header_row, self.workbook_type) {
+                (None, _) => self.sheets.worksheet_range(name)?,
+                // the rows before the header aren't kept while parsing
+                (Some(header_row), WorkbookType::Xlsx | WorkbookType::Xlsb) => {
+                    self.sheets.with_header_row(HeaderRow::Row(header_row));
+                    let range = self.sheets.worksheet_range(name);
+                    self.sheets.with_header_row(HeaderRow::FirstNonEmptyRow);
+                    range?
+                }
+                // calamine slices the parsed range of these formats and panics
+                // for a header after the last row
+                (Some(header_row), WorkbookType::Xls | WorkbookType::Ods) => {
+                    let range = self.sheets.worksheet_range(name)?;
+                    match (range.start(), range.end()) {
+                        (Some(start), Some(end)) if header_row <= end.0 => {
+                            range.range((header_row, start.1), end)
+                        }
+                        (Some(_), Some(_)) => Range::empty(),
+                        _ => range,
+                    }
+                }
+            };
+            let merge_cells_range = self.sheets.worksheet_merge_cells(name)?.map(|merged| {
+                let first_row = header_row.unwrap_or(0);
+                merged
+                    .into_iter()
+                    .filter(|d| d.start.0 >= first_row)
+                    .collect()
+            });
+            return Ok(CalamineSheet::new(
+                name.to_owned(),
+                range,
+                merge_cells_range,
+            ));
+        }
+        let (mut range, rows) = self
+            .sheets
+            .worksheet_range_rows(name, header_row, skiprows, nrows)?;
+        if let (Some(_), Some(rows), Some(start), Some(end)) =
+            (header_row, &rows, range.start(), range.end())
+        {
+            // anchored at the header row as calamine does
+            if start.0 > rows.start {
+                range = range.range((rows.start, start.1), end);
+            }
+        }
+        // merged cells overlapping the rows read are clipped to them
+        let merge_cells_range = self.sheets.worksheet_merge_cells(name)?.map(|merged| {
+            let Some(rows) = rows else {
+                return Vec::new();
+            };
+            merged
+                .into_iter()
+                .filter(|d| d.start.0 < rows.end && d.end.0 >= rows.start)
+                .map(|d| calamine::Dimensions {
+                    start: (d.start.0.max(rows.start), d.start.1),
+                    end: (d.end.0.min(rows.end - 1), d.end.1),
+                })
+                .collect()
+        });
         Ok(CalamineSheet::new(
             name.to_owned(),
             range,
@@ -364,13 +1103,119 @@ impl CalamineWorkbook {
         ))
     }
 
-    fn get_sheet_by_index(&mut self, index: usize) -> PyResult<CalamineSheet> {
+    /// XLSX and XLSB sheets are streamed from a workbook opened for the iterator,
+    /// the other formats have no cell reader and are read as a whole.
+    fn iter_sheet_rows(
+        &mut self,
+        name: &str,
+        usecols: Option<UseCols>,
+        empty_value: EmptyValue,
+    ) -> PyResult<CalamineRowIterator> {
+        match self.workbook_type {
+            WorkbookType::Xlsx | WorkbookType::Xlsb => {
+                if let SheetsEnum::None = self.sheets {
+                    return Err(Error::WorkbookClosed.into());
+                }
+                let sheets = open_sheets(self.source.open()?, self.workbook_type)
+                    .map_err(Error::Calamine)?;
+                Ok(CalamineRowIterator::from_sheets(
+                    sheets,
+                    name,
+                    usecols,
+                    empty_value,
+                )?)
+            }
+            WorkbookType::Xls | WorkbookType::Ods => Ok(CalamineRowIterator::from_range(
+                self.sheets.worksheet_range(name)?,
+                usecols,
+                empty_value,
+            )),
+        }
+    }
+
+    /// Reads the formulas of a sheet as a grid of the same shape as `CalamineSheet.to_python`,
+    /// so a position gives both the formula and its cached value.
+    fn get_sheet_formulas(
+        &mut self,
+        name: &str,
+        skip_empty_area: bool,
+    ) -> PyResult<Vec<Vec<String>>> {
+        let range = self.sheets.worksheet_range(name)?;
+        let formulas = self.sheets.worksheet_formula(name)?;
+        // the grid is the value range, formulas without a cached value outside of it are dropped
+        let (Some(start), Some(end)) = (range.start(), range.end()) else {
+            return Ok(Vec::new());
+        };
+        let start = if skip_empty_area { start } else { (0, 0) };
+
+        let mut grid = vec![
+            vec![String::new(); (end.1 - start.1 + 1) as usize];
+            (end.0 - start.0 + 1) as usize
+        ];
+        let formulas_start = formulas.start().unwrap_or_default();
+        for (row, col, formula) in formulas.used_cells() {
+            let row = formulas_start.0 + row as u32;
+            let col = formulas_start.1 + col as u32;
+            if (start.0..=end.0).contains(&row) && (start.1..=end.1).contains(&col) {
+                grid[(row - start.0) as usize][(col - start.1) as usize] = formula.clone();
+            }
+        }
+        Ok(grid)
+    }
+
+    fn get_sheet_by_index(
+        &mut self,
+        index: usize,
+        nrows: Option<u32>,
+        skiprows: u32,
+        header_row: Option<u32>,
+    ) -> PyResult<CalamineSheet> {
         let name = self
             .sheet_names
             .get(index)
             .ok_or_else(|| WorksheetNotFound::new_err(format!("Worksheet '{index}' not found")))?
             .to_string();
-        self.get_sheet_by_name(&name)
+        self.get_sheet_by_name(&name, nrows, skiprows, header_row)
+    }
+
+    /// Reads the area of a sheet, only the merged cells inside it are kept.
+    fn get_range(&mut self, reference: &CellReference) -> PyResult<CalamineSheet> {
+        let range = self.sheets.worksheet_range(&reference.sheet)?;
+        // the reference is clipped to the cells of the sheet, it may span whole columns
+        let start = range.start().map(|start| {
+            (
+                start.0.max(reference.start.0),
+                start.1.max(reference.start.1),
+            )
+        });
+        let end = range
+            .end()
+            .map(|end| (end.0.min(reference.end.0), end.1.min(reference.end.1)));
+        let range = match (start, end) {
+            (Some(start), Some(end)) if start.0 <= end.0 && start.1 <= end.1 => {
+                range.range(start, end)
+            }
+            _ => calamine::Range::empty(),
+        };
+        let merge_cells_range =
+            self.sheets
+                .worksheet_merge_cells(&reference.sheet)?
+                .map(|merged| {
+                    merged
+                        .into_iter()
+                        .filter(|d| {
+                            d.start.0 >= reference.start.0
+                                && d.start.1 >= reference.start.1
+                                && d.end.0 <= reference.end.0
+                                && d.end.1 <= reference.end.1
+                        })
+                        .collect()
+                });
+        Ok(CalamineSheet::new(
+            reference.sheet.clone(),
+            range,
+            merge_cells_range,
+        ))
     }
 
     fn get_table_by_name(&mut self, name: &str) -> PyResult<CalamineTable> {
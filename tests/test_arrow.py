@@ -0,0 +1,201 @@
+from datetime import date, datetime, time, timedelta
+from pathlib import Path
+
+import pytest
+from python_calamine import CalamineWorkbook
+
+from tests.utils import replace_in_zip
+
+try:
+    import pyarrow as pa
+except ImportError:
+    pa = None
+
+PATH = Path(__file__).parent / "data"
+
+
+def test_arrow_c_stream_capsule():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    capsule = reader.get_sheet_by_index(0).__arrow_c_stream__()
+
+    assert type(capsule).__name__ == "PyCapsule"
+    assert "arrow_array_stream" in repr(capsule)
+
+
+@pytest.mark.skipif(not pa, reason="pyarrow is required")
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb"])
+def test_sheet_to_arrow(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+    table = reader.get_sheet_by_name("Sheet1", header_row=0).to_arrow()
+
+    assert table.column_names == [f"Unnamed: {i}" for i in range(10)]
+    assert table.schema.types == [
+        pa.large_string(),
+        pa.float64(),
+        pa.float64(),
+        pa.bool_(),
+        pa.bool_(),
+        pa.date32(),
+        pa.timestamp("us"),
+        pa.time64("us"),
+        pa.duration("us"),
+        pa.duration("us"),
+    ]
+    assert table.to_pylist() == [
+        {
+            "Unnamed: 0": "String",
+            "Unnamed: 1": 1.0,
+            "Unnamed: 2": 1.1,
+            "Unnamed: 3": True,
+            "Unnamed: 4": False,
+            "Unnamed: 5": date(2010, 10, 10),
+            "Unnamed: 6": datetime(2010, 10, 10, 10, 10, 10),
+            "Unnamed: 7": time(10, 10, 10),
+            "Unnamed: 8": timedelta(
+                hours=10, minutes=10, seconds=10, microseconds=100000
+            ),
+            "Unnamed: 9": timedelta(hours=255, minutes=10, seconds=10),
+        }
+    ]
+
+
+@pytest.mark.skipif(not pa, reason="pyarrow is required")
+def test_sheet_to_arrow_mixed():
+    reader = CalamineWorkbook.from_object(PATH / "table-multiple.xlsx")
+    table = pa.table(reader.get_sheet_by_name("Sheet3"))
+
+    # the second table of the sheet is read as strings, empty cells are nulls
+    assert table.schema.types == [pa.large_string(), pa.large_string()]
+    assert table.column("Item").to_pylist() == [
+        "1.0",
+        "2.0",
+        "3.0",
+        "4.0",
+        None,
+        "Item",
+        "1.0",
+        "2.0",
+        "3.0",
+        "4.0",
+    ]
+
+    table = reader.get_sheet_by_name("Sheet2").to_arrow()
+    assert table.schema.types == [pa.float64(), pa.float64()]
+    assert table.column("Price").to_pylist() == [0.85, 0.75, 0.55, 0.45]
+
+
+@pytest.mark.skipif(not pa, reason="pyarrow is required")
+def test_sheet_to_arrow_mixed_floats(tmp_path):
+    values = [1e16, 1e-05, 0.0001, 1e15, -2.5e-07, 1.5e300, 1.2345678901234568e16]
+    cells = "".join(
+        f'<row r="{i}"><c r="A{i}"><v>{value!r}</v></c></row>'
+        for i, value in enumerate(values, start=2)
+    )
+    path = tmp_path / "floats.xlsx"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        path,
+        "xl/worksheets/sheet2.xml",
+        b"<sheetData/>",
+        b'<sheetData><row r="1"><c r="A1" t="inlineStr"><is><t>Value</t></is></c>'
+        + b"</row>"
+        + cells.encode()
+        + b'<row r="9"><c r="A9" t="inlineStr"><is><t>text</t></is></c></row>'
+        + b"</sheetData>",
+    )
+    table = CalamineWorkbook.from_object(path).get_sheet_by_name("Sheet2").to_arrow()
+
+    # floats of a string column are formatted as repr() does
+    assert table.schema.types == [pa.large_string()]
+    assert table.column("Value").to_pylist() == [repr(v) for v in values] + ["text"]
+
+
+@pytest.mark.skipif(not pa, reason="pyarrow is required")
+def test_sheet_to_arrow_empty():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    table = reader.get_sheet_by_name("Sheet2").to_arrow()
+
+    assert table.num_columns == 0
+    assert table.num_rows == 0
+
+
+@pytest.mark.skipif(not pa, reason="pyarrow is required")
+def test_table_to_arrow():
+    reader = CalamineWorkbook.from_object(
+        PATH / "table-multiple.xlsx", load_tables=True
+    )
+    table = reader.get_table_by_name("Inventory").to_arrow()
+
+    assert table.column_names == ["Item", "Type", "Quantity"]
+    assert table.schema.types == [pa.float64(), pa.large_string(), pa.float64()]
+    assert table.to_pydict() == {
+        "Item": [1.0, 2.0, 3.0, 4.0],
+        "Type": ["Apple", "Banana", "Orange", "Pear"],
+        "Quantity": [50.0, 200.0, 60.0, 100.0],
+    }
+
+
+def test_iter_batches():
+    reader = CalamineWorkbook.from_object(PATH / "table-multiple.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet3")
+
+    assert [len(batch) for batch in sheet.iter_batches(batch_size=4)] == [4, 4, 2]
+    assert [len(batch) for batch in sheet.iter_batches(n_rows=5)] == [5]
+    # an empty batch has the schema
+    assert [len(batch) for batch in sheet.iter_batches(n_rows=0)] == [0]
+
+    with pytest.raises(ValueError, match="Unknown column: 'Price'"):
+        sheet.iter_batches(columns=["Price"])
+    with pytest.raises(ValueError, match="batch_size must be positive"):
+        sheet.iter_batches(batch_size=0)
+
+
+@pytest.mark.skipif(not pa, reason="pyarrow is required")
+def test_iter_batches_infer_schema_length():
+    reader = CalamineWorkbook.from_object(PATH / "table-multiple.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet3")
+
+    # values of another type after the inferred rows are nulls
+    tables = [pa.table(batch) for batch in sheet.iter_batches(infer_schema_length=4)]
+    assert tables[0].schema.types == [pa.float64(), pa.float64()]
+    assert tables[0].column("Item").to_pylist()[3:7] == [4.0, None, None, 1.0]
+
+    tables = [pa.table(batch) for batch in sheet.iter_batches(infer_schema_length=0)]
+    assert tables[0].schema.types == [pa.null(), pa.null()]
+
+    # or strings in a string column
+    tables = [
+        pa.table(batch) for batch in sheet.iter_batches(infer_schema_length=6, n_rows=6)
+    ]
+    assert tables[0].schema.types == [pa.large_string(), pa.large_string()]
+    assert tables[0].column("Item").to_pylist()[4:] == [None, "Item"]
+
+
+@pytest.mark.skipif(not pa, reason="pyarrow is required")
+def test_iter_batches_to_arrow():
+    reader = CalamineWorkbook.from_object(
+        PATH / "table-multiple.xlsx", load_tables=True
+    )
+    sheet = reader.get_sheet_by_name("Sheet3")
+
+    # the types are inferred from all rows, not from the rows of a batch
+    tables = [
+        pa.table(batch)
+        for batch in sheet.iter_batches(columns=["Units"], n_rows=6, batch_size=4)
+    ]
+    assert [table.column_names for table in tables] == [["Units"], ["Units"]]
+    assert [table.schema.types for table in tables] == [
+        [pa.large_string()],
+        [pa.large_string()],
+    ]
+    assert [table.column("Units").to_pylist() for table in tables] == [
+        ["1000.0", "500.0", "200.0", "50.0"],
+        [None, "Units"],
+    ]
+
+    table = reader.get_table_by_name("Inventory")
+    batches = list(table.iter_batches(columns=["Quantity", "Item"], batch_size=3))
+    assert [pa.table(batch).to_pydict() for batch in batches] == [
+        {"Quantity": [50.0, 200.0, 60.0], "Item": [1.0, 2.0, 3.0]},
+        {"Quantity": [100.0], "Item": [4.0]},
+    ]
@@ -1,16 +1,22 @@
+import shutil
+import zipfile
 from datetime import date, datetime, time, timedelta
 from io import BytesIO
 from pathlib import Path
 
 import pytest
 from python_calamine import (
+    CalamineError,
     CalamineWorkbook,
+    InvalidPasswordError,
     PasswordError,
     WorkbookClosed,
     WorksheetNotFound,
     ZipError,
 )
 
+from tests.utils import replace_in_zip
+
 PATH = Path(__file__).parent / "data"
 
 
@@ -222,6 +228,83 @@ def test_xlsx_iter_rows():
     assert data == list(reader.get_sheet_by_index(0).iter_rows())
 
 
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb", "base.ods"])
+def test_iter_sheet_rows(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+
+    for name in reader.sheet_names:
+        sheet = reader.get_sheet_by_name(name)
+        assert list(reader.iter_sheet_rows(name)) == sheet.to_python(
+            skip_empty_area=False
+        )
+
+
+def test_iter_sheet_rows_lazy():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    rows = reader.iter_sheet_rows("Sheet3")
+
+    assert next(rows) == ["", "", "", ""]
+    assert next(rows) == ["", "line1", "line1", "line1"]
+    del rows
+
+    assert len(list(reader.iter_sheet_rows("Sheet3"))) == 4
+
+
+def test_iter_sheet_rows_without_dimension(tmp_path):
+    path = tmp_path / "base.xlsx"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        path,
+        "xl/worksheets/sheet3.xml",
+        b'<dimension ref="B2:D4"/>',
+        b"",
+    )
+    reader = CalamineWorkbook.from_object(path)
+
+    # the empty first row is padded to the width of the row after it
+    assert list(reader.iter_sheet_rows("Sheet3")) == reader.get_sheet_by_name(
+        "Sheet3"
+    ).to_python(skip_empty_area=False)
+
+
+def test_iter_sheet_rows_gap(tmp_path):
+    path = tmp_path / "base.xlsx"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        path,
+        "xl/worksheets/sheet2.xml",
+        b"<sheetData/>",
+        b'<sheetData><row r="100000">'
+        b'<c r="B100000" t="inlineStr"><is><t>last</t></is></c>'
+        b"</row></sheetData>",
+    )
+    reader = CalamineWorkbook.from_object(path)
+    rows = reader.iter_sheet_rows("Sheet2")
+
+    assert next(rows) == ["", ""]
+    assert sum(1 for _ in rows) == 99999
+    assert list(reader.iter_sheet_rows("Sheet2"))[-1] == ["", "last"]
+
+
+def test_iter_sheet_rows_password():
+    reader = CalamineWorkbook.from_object(PATH / "password.xlsx", password="1234")
+
+    assert list(reader.iter_sheet_rows("Sheet1")) == reader.get_sheet_by_name(
+        "Sheet1"
+    ).to_python(skip_empty_area=False)
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb", "base.ods"])
+def test_iter_sheet_rows_errors(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+    with pytest.raises(WorksheetNotFound):
+        reader.iter_sheet_rows("Sheet4")
+
+    reader.close()
+    with pytest.raises(WorkbookClosed):
+        reader.iter_sheet_rows("Sheet1")
+
+
 def test_nrows():
     reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
     sheet = reader.get_sheet_by_name("Sheet3")
@@ -260,6 +343,302 @@ def test_nrows():
     ]
 
 
+def test_usecols():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet3")
+
+    assert sheet.to_python(usecols=[1, 3]) == [
+        ["line1", "line1"],
+        ["line2", "line2"],
+        ["line3", "line3"],
+    ]
+    assert sheet.to_python(usecols="c:D,a") == [
+        ["line1", "line1"],
+        ["line2", "line2"],
+        ["line3", "line3"],
+    ]
+    assert sheet.to_python(skip_empty_area=False, usecols="A:B", nrows=2) == [
+        ["", ""],
+        ["", "line1"],
+    ]
+    assert sheet.to_python(usecols=[10]) == [[], [], []]
+    assert list(sheet.iter_rows(usecols=[0, 2])) == [
+        [""],
+        ["line1"],
+        ["line2"],
+        ["line3"],
+    ]
+
+    sheet = reader.get_sheet_by_name("Sheet1")
+    assert sheet.to_python(usecols=lambda v: isinstance(v, (str, bool))) == [
+        ["String", True, False]
+    ]
+    assert list(sheet.iter_rows(usecols=lambda v: v != "")) == [[], []]
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xlsb"])
+def test_empty_value(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+    sheet = reader.get_sheet_by_name("Sheet3")
+
+    assert sheet.to_python(skip_empty_area=False, nrows=2) == [
+        ["", "", "", ""],
+        ["", "line1", "line1", "line1"],
+    ]
+    assert sheet.to_python(skip_empty_area=False, nrows=2, empty_value=None) == [
+        [None, None, None, None],
+        [None, "line1", "line1", "line1"],
+    ]
+    assert sheet.to_python(skip_empty_area=False, usecols="A", empty_value="-") == [
+        ["-"],
+        ["-"],
+        ["-"],
+        ["-"],
+    ]
+    # the rows before the data
+    assert list(sheet.iter_rows(empty_value=None))[:2] == [
+        [None, None, None],
+        ["line1", "line1", "line1"],
+    ]
+    assert list(sheet.iter_rows(usecols=[1], empty_value=None)) == [
+        [None],
+        ["line1"],
+        ["line2"],
+        ["line3"],
+    ]
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb", "base.ods"])
+def test_iter_sheet_rows_usecols(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+    data = reader.get_sheet_by_name("Sheet1").to_python(skip_empty_area=False)
+
+    assert list(reader.iter_sheet_rows("Sheet1", usecols="A,C:D")) == [
+        [row[0], row[2], row[3]] for row in data
+    ]
+    assert list(reader.iter_sheet_rows("Sheet1", usecols=[1, 100])) == [
+        [row[1]] for row in data
+    ]
+
+    # the predicate gets the first row with a value, not the empty first row
+    rows = reader.iter_sheet_rows("Sheet1", usecols=lambda v: v in ("String", 1.1))
+    assert list(rows) == [[row[0], row[2]] for row in data]
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb", "base.ods"])
+def test_iter_sheet_rows_empty_value(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+    data = reader.get_sheet_by_name("Sheet1").to_python(
+        skip_empty_area=False, empty_value=None
+    )
+
+    assert list(reader.iter_sheet_rows("Sheet1", empty_value=None)) == data
+    assert list(reader.iter_sheet_rows("Sheet1", usecols=[0], empty_value=None)) == [
+        [row[0]] for row in data
+    ]
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xlsb"])
+def test_iter_sheet_rows_usecols_leading_empty_row(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+
+    rows = reader.iter_sheet_rows("Sheet3", usecols=lambda v: v == "line1")
+    assert list(rows) == [
+        ["", "", ""],
+        ["line1", "line1", "line1"],
+        ["line2", "line2", "line2"],
+        ["line3", "line3", "line3"],
+    ]
+
+
+@pytest.mark.parametrize("usecols", ["A:", "1:3", "C:A", [-1], 1.5])
+def test_usecols_errors(usecols):
+    sheet = CalamineWorkbook.from_object(PATH / "base.xlsx").get_sheet_by_index(0)
+
+    with pytest.raises((ValueError, TypeError)):
+        sheet.to_python(usecols=usecols)
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xlsb"])
+def test_records(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+    sheet = reader.get_sheet_by_name("Sheet3")
+
+    # duplicated names get a suffix
+    assert sheet.to_records() == [
+        {"line1": "line2", "line1_1": "line2", "line1_2": "line2"},
+        {"line1": "line3", "line1_1": "line3", "line1_2": "line3"},
+    ]
+    assert list(sheet.iter_dicts(header_row=1)) == [
+        {"line2": "line3", "line2_1": "line3", "line2_2": "line3"},
+    ]
+    assert sheet.to_records(header_row=2) == []
+    assert sheet.to_records(header_row=10) == []
+    assert list(sheet.iter_dicts(header_row=10)) == []
+
+    # blank names are replaced by the column position
+    sheet = reader.get_sheet_by_name("Sheet3", header_row=0)
+    assert list(sheet.iter_dicts())[0] == {
+        "Unnamed: 0": "line1",
+        "Unnamed: 1": "line1",
+        "Unnamed: 2": "line1",
+    }
+
+
+def test_records_empty_value():
+    reader = CalamineWorkbook.from_object(PATH / "table-multiple.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet3")
+
+    # the row between the two tables is empty
+    assert sheet.to_records()[4] == {"Item": "", "Units": ""}
+    assert sheet.to_records(empty_value=None)[4] == {"Item": None, "Units": None}
+    assert list(sheet.iter_dicts(empty_value="-"))[4] == {"Item": "-", "Units": "-"}
+    assert list(sheet.iter_dicts(header_row=0, empty_value=None))[3:5] == [
+        {"Item": 4.0, "Units": 50.0},
+        {"Item": None, "Units": None},
+    ]
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xlsb"])
+def test_get_sheet_nrows_skiprows(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+
+    # rows are counted from the first non-empty row, like to_python(nrows=...)
+    sheet = reader.get_sheet_by_name("Sheet3", nrows=2)
+    assert sheet.start == (1, 1)
+    assert sheet.to_python() == [
+        ["line1", "line1", "line1"],
+        ["line2", "line2", "line2"],
+    ]
+
+    sheet = reader.get_sheet_by_name("Sheet3", nrows=1, skiprows=1)
+    assert sheet.start == (2, 1)
+    assert sheet.to_python() == [["line2", "line2", "line2"]]
+
+    sheet = reader.get_sheet_by_index(2, skiprows=2)
+    assert sheet.to_python() == [["line3", "line3", "line3"]]
+
+    assert reader.get_sheet_by_name("Sheet3", nrows=0).to_python() == []
+    assert reader.get_sheet_by_name("Sheet3", skiprows=10).to_python() == []
+    assert reader.get_sheet_by_name("Sheet2", nrows=5).to_python() == []
+
+    sheet = reader.get_sheet_by_name("Sheet3")
+    for nrows in range(5):
+        assert reader.get_sheet_by_name(
+            "Sheet3", nrows=nrows
+        ).to_python() == sheet.to_python(nrows=nrows)
+
+
+def test_get_sheet_nrows_columns(tmp_path):
+    path = tmp_path / "base.xlsx"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        tmp_path / "dimension.xlsx",
+        "xl/worksheets/sheet2.xml",
+        b'<dimension ref="A1"/>',
+        b'<dimension ref="A1:E5"/>',
+    )
+    replace_in_zip(
+        tmp_path / "dimension.xlsx",
+        path,
+        "xl/worksheets/sheet2.xml",
+        b"<sheetData/>",
+        b'<sheetData><row r="1"><c r="C1" t="inlineStr"><is><t>h</t></is></c></row>'
+        b'<row r="5"><c r="A5"><v>1</v></c><c r="E5"><v>2</v></c></row></sheetData>',
+    )
+    reader = CalamineWorkbook.from_object(path)
+
+    # the columns of a later row are kept, the rows read are a prefix of the sheet
+    sheet = reader.get_sheet_by_name("Sheet2")
+    assert sheet.start == (0, 0)
+    assert sheet.to_python()[0] == ["", "", "h", "", ""]
+
+    sheet = reader.get_sheet_by_name("Sheet2", nrows=1)
+    assert sheet.start == (0, 0)
+    assert sheet.to_python() == [["", "", "h", "", ""]]
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb", "base.ods"])
+def test_get_sheet_nrows_skiprows_formats(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+    data = reader.get_sheet_by_name("Sheet1").to_python()
+
+    assert reader.get_sheet_by_name("Sheet1", nrows=1).to_python() == data
+    assert reader.get_sheet_by_name("Sheet1", nrows=2).to_python() == data
+    assert reader.get_sheet_by_name("Sheet1", skiprows=1).to_python() == []
+    assert reader.get_sheet_by_name("Sheet1", nrows=0).to_python() == []
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls"])
+def test_get_sheet_nrows_merged_cells(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+
+    # the merged cells have no value, the rows are counted from the header row
+    sheet = reader.get_sheet_by_name("Merged Cells", header_row=0, nrows=1)
+    assert sheet.merged_cell_ranges == [((0, 0), (0, 0)), ((0, 1), (0, 1))]
+
+    sheet = reader.get_sheet_by_name("Merged Cells", header_row=0, skiprows=1)
+    assert sheet.merged_cell_ranges == [((1, 0), (1, 0)), ((1, 1), (1, 1))]
+
+    sheet = reader.get_sheet_by_name("Merged Cells", header_row=0, skiprows=2)
+    assert sheet.merged_cell_ranges == []
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb", "base.ods"])
+def test_get_sheet_nrows_errors(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+    with pytest.raises(WorksheetNotFound):
+        reader.get_sheet_by_name("Sheet4", nrows=1)
+
+    reader.close()
+    with pytest.raises(WorkbookClosed):
+        reader.get_sheet_by_name("Sheet1", nrows=1)
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xlsb"])
+def test_get_sheet_header_row(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+
+    sheet = reader.get_sheet_by_name("Sheet3", header_row=0)
+    assert sheet.start == (0, 1)
+    assert sheet.to_python() == [
+        ["", "", ""],
+        ["line1", "line1", "line1"],
+        ["line2", "line2", "line2"],
+        ["line3", "line3", "line3"],
+    ]
+
+    sheet = reader.get_sheet_by_index(2, header_row=2)
+    assert sheet.start == (2, 1)
+    assert sheet.to_python() == [
+        ["line2", "line2", "line2"],
+        ["line3", "line3", "line3"],
+    ]
+
+    sheet = reader.get_sheet_by_name("Sheet3", header_row=1, skiprows=1, nrows=1)
+    assert sheet.to_python() == [["line2", "line2", "line2"]]
+
+    sheet = reader.get_sheet_by_name("Sheet3", header_row=0, nrows=2)
+    assert sheet.start == (0, 1)
+    assert sheet.to_python() == [["", "", ""], ["line1", "line1", "line1"]]
+
+    # the default is still the first non-empty row
+    assert reader.get_sheet_by_name("Sheet3").start == (1, 1)
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb", "base.ods"])
+def test_get_sheet_header_row_formats(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+    data = reader.get_sheet_by_name("Sheet1").to_python()
+
+    sheet = reader.get_sheet_by_name("Sheet1", header_row=0)
+    assert sheet.start == (0, 0)
+    assert sheet.to_python() == [[""] * len(data[0])] + data
+
+    assert reader.get_sheet_by_name("Sheet1", header_row=1).to_python() == data
+    assert reader.get_sheet_by_name("Sheet1", header_row=5).to_python() == []
+
+
 @pytest.mark.parametrize(
     "path",
     [
@@ -289,6 +668,70 @@ def test_password_errors(path):
         CalamineWorkbook.from_object(path)
 
 
+@pytest.mark.parametrize(
+    ("path", "sheet_names"),
+    [
+        (PATH / "password.xlsx", ["Sheet1", "Sheet2", "Sheet3"]),
+        (PATH / "password.xls", ["Sheet1", "Sheet2"]),
+        (PATH / "password.xlsb", ["Sheet1", "Sheet2", "Sheet3"]),
+        (PATH / "password.ods", ["Sheet1", "Sheet2"]),
+    ],
+)
+def test_password(path, sheet_names):
+    for obj in (path, path.read_bytes(), BytesIO(path.read_bytes())):
+        reader = CalamineWorkbook.from_object(obj, password="1234")
+
+        assert reader.sheet_names == sheet_names
+        assert reader.get_sheet_by_index(0).to_python()[0][:3] == ["String", 1, 1.1]
+
+
+@pytest.mark.parametrize(
+    "path",
+    [
+        PATH / "password.xlsx",
+        PATH / "password.xls",
+        PATH / "password.xlsb",
+        PATH / "password.ods",
+    ],
+)
+def test_invalid_password(path):
+    with pytest.raises(InvalidPasswordError):
+        CalamineWorkbook.from_path(path, password="4321")
+
+    # InvalidPasswordError is a PasswordError
+    with pytest.raises(PasswordError):
+        CalamineWorkbook.from_path(path, password="4321")
+
+
+def test_password_ods_without_checksum():
+    # Blowfish CFB entries without checksums, as written by older OpenOffice versions
+    path = PATH / "password_blowfish.ods"
+
+    reader = CalamineWorkbook.from_path(path, password="1234")
+    assert reader.sheet_names == ["Sheet1", "Sheet2"]
+    assert reader.get_sheet_by_index(0).to_python()[0][:3] == ["String", 1, 1.1]
+
+    with pytest.raises(InvalidPasswordError):
+        CalamineWorkbook.from_path(path, password="4321")
+
+
+def test_password_spin_count():
+    data = (PATH / "password.xlsb").read_bytes()
+    # the saltSize is cleared to keep the length of the EncryptionInfo stream
+    data = data.replace(
+        b'spinCount="100000" saltSize="16"', b'spinCount="99999999" saltSize=""'
+    )
+
+    with pytest.raises(PasswordError, match="spin count 99999999"):
+        CalamineWorkbook.from_object(data, password="1234")
+
+
+def test_password_unencrypted():
+    reader = CalamineWorkbook.from_path(PATH / "base.xlsx", password="1234")
+
+    assert reader.sheet_names == ["Sheet1", "Sheet2", "Sheet3", "Merged Cells"]
+
+
 @pytest.mark.parametrize(
     "path",
     [
@@ -356,6 +799,40 @@ def test_path_or_filelike(obj):
     CalamineWorkbook.from_object(obj)
 
 
+@pytest.mark.parametrize(
+    "factory",
+    [bytes, bytearray, memoryview],
+)
+@pytest.mark.parametrize(
+    "path",
+    [
+        PATH / "base.xlsx",
+        PATH / "base.xls",
+        PATH / "base.xlsb",
+        PATH / "base.ods",
+    ],
+)
+def test_buffer(factory, path):
+    expected = CalamineWorkbook.from_path(path).get_sheet_by_index(0).to_python()
+
+    data = factory(open(path, "rb").read())
+
+    assert CalamineWorkbook.from_buffer(data).get_sheet_by_index(0).to_python() == (
+        expected
+    )
+    assert CalamineWorkbook.from_object(data).get_sheet_by_index(0).to_python() == (
+        expected
+    )
+
+
+def test_buffer_non_contiguous():
+    data = bytes(b for b in open(PATH / "base.xlsx", "rb").read() for _ in range(2))
+
+    reader = CalamineWorkbook.from_buffer(memoryview(data)[::2])
+
+    assert reader.sheet_names == ["Sheet1", "Sheet2", "Sheet3", "Merged Cells"]
+
+
 def test_path_or_filelike_error():
     with pytest.raises(TypeError):
         CalamineWorkbook.from_object(object())
@@ -382,3 +859,135 @@ def test_datetime_overflow():
 
     # 2994626.0 = 10099-01-01
     assert reader.get_sheet_by_index(0).to_python() == [["date"], [2994626.0]]
+
+
+class NonSeekableIO:
+    def __init__(self, data):
+        self._buffer = BytesIO(data)
+
+    def read(self, size=-1):
+        return self._buffer.read(size)
+
+    def seekable(self):
+        return False
+
+
+def test_filelike_non_seekable():
+    reader = CalamineWorkbook.from_filelike(
+        NonSeekableIO(open(PATH / "base.xlsx", "rb").read())
+    )
+
+    assert reader.sheet_names == ["Sheet1", "Sheet2", "Sheet3", "Merged Cells"]
+
+
+@pytest.mark.parametrize(
+    "path",
+    [
+        PATH / "base.xlsx",
+        PATH / "base.xls",
+        PATH / "base.xlsb",
+        PATH / "base.ods",
+    ],
+)
+def test_filelike_streaming(path):
+    expected = CalamineWorkbook.from_path(path).get_sheet_by_index(0).to_python()
+
+    with open(path, "rb") as f:
+        reader = CalamineWorkbook.from_filelike(f)
+
+        assert reader.get_sheet_by_index(0).to_python() == expected
+
+
+def test_filelike_moved_between_reads(tmp_path):
+    # a stored sheet large enough to be read in several chunks
+    path = tmp_path / "rows.xlsx"
+    rows = "".join(
+        f'<row r="{i}"><c r="A{i}"><v>{i}</v></c></row>' for i in range(1, 20001)
+    )
+    with (
+        zipfile.ZipFile(PATH / "base.xlsx") as src,
+        zipfile.ZipFile(path, "w") as dst,
+    ):
+        for item in src.infolist():
+            content = src.read(item.filename)
+            if item.filename == "xl/worksheets/sheet2.xml":
+                content = content.replace(
+                    b"<sheetData/>", f"<sheetData>{rows}</sheetData>".encode()
+                )
+                item.compress_type = zipfile.ZIP_STORED
+            dst.writestr(item, content)
+
+    with open(path, "rb") as f:
+        f.seek(100)
+        reader = CalamineWorkbook.from_filelike(f)
+        values = []
+        for row in reader.iter_sheet_rows("Sheet2"):
+            values.append(row[0])
+            # user code moves the file between the reads of the workbook
+            f.seek(10)
+            f.read(5)
+        assert values == [float(i) for i in range(1, 20001)]
+
+        f.seek(0)
+        assert reader.get_sheet_by_index(0).to_python()[0][0] == "String"
+
+
+@pytest.mark.parametrize(
+    "path",
+    [
+        PATH / "base.xlsx",
+        PATH / "base.xls",
+        PATH / "base.xlsb",
+        PATH / "base.ods",
+    ],
+)
+def test_path_mmap(path):
+    expected = CalamineWorkbook.from_path(path).get_sheet_by_index(0).to_python()
+
+    reader = CalamineWorkbook.from_path(path, mmap=True)
+
+    assert reader.get_sheet_by_index(0).to_python() == expected
+
+
+def test_mmap_not_path():
+    with open(PATH / "base.xlsx", "rb") as f:
+        with pytest.raises(ValueError, match="mmap is supported for paths only"):
+            CalamineWorkbook.from_object(f, mmap=True)
+
+        with pytest.raises(ValueError, match="mmap is supported for paths only"):
+            CalamineWorkbook.from_object(f.read(), mmap=True)
+
+
+@pytest.mark.parametrize(
+    ("path", "format"),
+    [
+        (PATH / "base.xlsx", "xlsx"),
+        (PATH / "base.xls", "xls"),
+        (PATH / "base.xlsb", "xlsb"),
+        (PATH / "base.ods", "ods"),
+    ],
+)
+def test_format(tmp_path, path, format):
+    expected = CalamineWorkbook.from_path(path).get_sheet_by_index(0).to_python()
+
+    wrong_extension = tmp_path / "workbook.xlsx"
+    if path.suffix == ".xlsx":
+        wrong_extension = tmp_path / "workbook.ods"
+    shutil.copy(path, wrong_extension)
+
+    reader = CalamineWorkbook.from_path(wrong_extension, format=format)
+    assert reader.get_sheet_by_index(0).to_python() == expected
+
+    with open(path, "rb") as f:
+        reader = CalamineWorkbook.from_filelike(f, format=format)
+        assert reader.get_sheet_by_index(0).to_python() == expected
+
+
+def test_format_unsupported():
+    with pytest.raises(ValueError):
+        CalamineWorkbook.from_path(PATH / "base.xlsx", format="csv")
+
+
+def test_format_not_detected():
+    with pytest.raises(CalamineError, match="tried: xls .*, xlsx .*, xlsb .*, ods"):
+        CalamineWorkbook.from_object(BytesIO(b"not a workbook"))
@@ -0,0 +1,175 @@
+from pathlib import Path
+
+import pytest
+from python_calamine import (
+    CalamineError,
+    CalamineWorkbook,
+    DefinedName,
+    DefinedNameNotFound,
+)
+
+from tests.utils import replace_in_zip
+
+PATH = Path(__file__).parent / "data"
+
+XLSX_DEFINED_NAMES = (
+    b"</sheets><definedNames>"
+    b'<definedName name="Data">Sheet1!$A$2:$C$2</definedName>'
+    b'<definedName name="Merged" localSheetId="3">'
+    b"'Merged Cells'!$A$1:$B$2</definedName>"
+    b'<definedName name="Constant">42</definedName>'
+    b"</definedNames>"
+)
+ODS_DEFINED_NAMES = (
+    b"<table:named-expressions>"
+    b'<table:named-range table:name="Data" table:base-cell-address="$Sheet1.$A$2"'
+    b' table:cell-range-address="$Sheet1.$A$2:.$C$2"></table:named-range>'
+    b"</table:named-expressions>"
+)
+
+
+def make_xlsx(tmp_path):
+    path = tmp_path / "defined_names.xlsx"
+    replace_in_zip(
+        PATH / "base.xlsx", path, "xl/workbook.xml", b"</sheets>", XLSX_DEFINED_NAMES
+    )
+    return path
+
+
+def make_ods(tmp_path):
+    path = tmp_path / "defined_names.ods"
+    replace_in_zip(
+        PATH / "base.ods",
+        path,
+        "content.xml",
+        b"<table:named-expressions/>",
+        ODS_DEFINED_NAMES,
+    )
+    return path
+
+
+def test_defined_names_xlsx(tmp_path):
+    reader = CalamineWorkbook.from_object(make_xlsx(tmp_path))
+
+    names = reader.defined_names
+    assert all(isinstance(name, DefinedName) for name in names)
+    assert [(n.name, n.scope, n.formula) for n in names] == [
+        ("Data", None, "Sheet1!$A$2:$C$2"),
+        ("Merged", "Merged Cells", "'Merged Cells'!$A$1:$B$2"),
+        ("Constant", None, "42"),
+    ]
+
+
+def test_defined_names_ods(tmp_path):
+    reader = CalamineWorkbook.from_object(make_ods(tmp_path))
+
+    assert [(n.name, n.scope, n.formula) for n in reader.defined_names] == [
+        ("Data", None, "$Sheet1.$A$2:.$C$2"),
+    ]
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb", "base.ods"])
+def test_defined_names_empty(path):
+    assert CalamineWorkbook.from_object(PATH / path).defined_names == []
+
+
+@pytest.mark.parametrize("make_path", [make_xlsx, make_ods])
+def test_get_range_by_name(make_path, tmp_path):
+    reader = CalamineWorkbook.from_object(make_path(tmp_path))
+
+    sheet = reader.get_range_by_name("Data")
+    assert sheet.name == "Sheet1"
+    assert sheet.start == (1, 0)
+    assert sheet.end == (1, 2)
+    assert sheet.to_python() == [["String", 1, 1.1]]
+
+
+@pytest.mark.parametrize(
+    ("formula", "start", "end", "data"),
+    [
+        ("Sheet1!$A$1:$XFD$1048576", (1, 0), (1, 9), None),
+        ("Sheet1!$A$1:$Z$200000", (1, 0), (1, 9), None),
+        ("Sheet1!$B:$C", (1, 1), (1, 2), [[1, 1.1]]),
+        ("Sheet1!$2:$2", (1, 0), (1, 9), None),
+        ("Sheet1!$5:$9", None, None, []),
+    ],
+)
+def test_get_range_by_name_clipped(tmp_path, formula, start, end, data):
+    path = tmp_path / "defined_names.xlsx"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        path,
+        "xl/workbook.xml",
+        b"</sheets>",
+        b"</sheets><definedNames>"
+        b'<definedName name="Area">' + formula.encode() + b"</definedName>"
+        b"</definedNames>",
+    )
+    reader = CalamineWorkbook.from_object(path)
+
+    # the area is clipped to the cells with values
+    sheet = reader.get_range_by_name("Area")
+    assert sheet.start == start
+    assert sheet.end == end
+    if data is None:
+        data = reader.get_sheet_by_name("Sheet1").to_python()
+    assert sheet.to_python() == data
+
+
+def test_get_range_by_name_merged_cells(tmp_path):
+    reader = CalamineWorkbook.from_object(make_xlsx(tmp_path))
+
+    sheet = reader.get_range_by_name("Merged")
+    assert sheet.name == "Merged Cells"
+    assert sheet.merged_cell_ranges == [((0, 0), (1, 0)), ((0, 1), (1, 1))]
+
+
+def test_get_range_by_name_errors(tmp_path):
+    reader = CalamineWorkbook.from_object(make_xlsx(tmp_path))
+
+    with pytest.raises(DefinedNameNotFound):
+        reader.get_range_by_name("Missing")
+
+    with pytest.raises(CalamineError, match="not a cell range"):
+        reader.get_range_by_name("Constant")
+
+
+def test_defined_names_scope_by_name(tmp_path):
+    path = tmp_path / "defined_names.xlsx"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        path,
+        "xl/workbook.xml",
+        b"</sheets>",
+        b"</sheets><definedNames>"
+        b'<definedName name="Data" localSheetId="1">Sheet2!$A$1</definedName>'
+        b'<definedName name="A&amp;B" localSheetId="3">'
+        b"'Merged Cells'!$A$1</definedName>"
+        b'<definedName name="Data">Sheet1!$A$2:$C$2</definedName>'
+        b"</definedNames>",
+    )
+    reader = CalamineWorkbook.from_object(path)
+
+    assert [(n.name, n.scope) for n in reader.defined_names] == [
+        ("Data", "Sheet2"),
+        ("A&B", "Merged Cells"),
+        ("Data", None),
+    ]
+    assert reader.get_range_by_name("Data").name == "Sheet1"
+
+
+def test_get_range_by_name_several_areas(tmp_path):
+    path = tmp_path / "defined_names.xlsx"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        path,
+        "xl/workbook.xml",
+        b"</sheets>",
+        b"</sheets><definedNames>"
+        b'<definedName name="Areas">Sheet1!$A$1:$B$2,Sheet1!$D$1</definedName>'
+        b"</definedNames>",
+    )
+    reader = CalamineWorkbook.from_object(path)
+
+    with pytest.raises(CalamineError, match="not a cell range"):
+        reader.get_range_by_name("Areas")
@@ -0,0 +1,194 @@
+from io import BytesIO
+from pathlib import Path
+
+import pytest
+from python_calamine import (
+    CalamineWorkbook,
+    FormatInfo,
+    WorkbookFormatEnum,
+    detect_format,
+)
+
+from tests.utils import replace_in_zip
+
+PATH = Path(__file__).parent / "data"
+
+XLSX_CONTENT_TYPE = (
+    b"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"
+)
+XLSM_CONTENT_TYPE = b"application/vnd.ms-excel.sheet.macroEnabled.main+xml"
+
+
+@pytest.mark.parametrize(
+    ("file", "format", "biff_version"),
+    [
+        ("base.xls", WorkbookFormatEnum.Xls, 8),
+        ("base.xlsx", WorkbookFormatEnum.Xlsx, None),
+        ("base.xlsb", WorkbookFormatEnum.Xlsb, None),
+        ("base.ods", WorkbookFormatEnum.Ods, None),
+    ],
+)
+def test_detect_format(file, format, biff_version):
+    info = detect_format(PATH / file)
+
+    assert isinstance(info, FormatInfo)
+    assert info.format == format
+    assert info.biff_version == biff_version
+    assert info.encrypted is False
+    assert info.encryption is None
+
+
+@pytest.mark.parametrize(
+    ("file", "format", "encryption"),
+    [
+        ("password.xls", WorkbookFormatEnum.Xls, "rc4"),
+        ("password.xlsx", WorkbookFormatEnum.EncryptedOoxml, "standard"),
+        ("password.xlsb", WorkbookFormatEnum.EncryptedOoxml, "agile"),
+        ("password.ods", WorkbookFormatEnum.Ods, "odf"),
+    ],
+)
+def test_detect_format_encrypted(file, format, encryption):
+    info = detect_format(PATH / file)
+
+    assert info.format == format
+    assert info.encrypted is True
+    assert info.encryption == encryption
+
+
+def test_detect_format_xlsm(tmp_path):
+    path = tmp_path / "base.xlsm"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        path,
+        "[Content_Types].xml",
+        XLSX_CONTENT_TYPE,
+        XLSM_CONTENT_TYPE,
+    )
+
+    assert detect_format(path).format == WorkbookFormatEnum.Xlsm
+
+
+def test_detect_format_ignores_extension(tmp_path):
+    path = tmp_path / "base.xls"
+    path.write_bytes((PATH / "base.xlsb").read_bytes())
+
+    assert detect_format(path).format == WorkbookFormatEnum.Xlsb
+
+
+@pytest.mark.parametrize(
+    "file", ["empty_file.xlsx", "empty_file.ods", "empty_file.xlsb"]
+)
+def test_detect_format_unknown(file):
+    assert detect_format(PATH / file).format == WorkbookFormatEnum.Unknown
+
+
+def test_detect_format_buffer():
+    data = (PATH / "base.ods").read_bytes()
+
+    assert detect_format(data).format == WorkbookFormatEnum.Ods
+    assert detect_format(memoryview(data)).format == WorkbookFormatEnum.Ods
+    assert detect_format(b"not a workbook").format == WorkbookFormatEnum.Unknown
+
+
+def test_detect_format_filelike_position():
+    with open(PATH / "base.xlsx", "rb") as f:
+        f.seek(10)
+        assert detect_format(f).format == WorkbookFormatEnum.Xlsx
+        assert f.tell() == 10
+
+    data = BytesIO((PATH / "base.xls").read_bytes())
+    assert detect_format(data).format == WorkbookFormatEnum.Xls
+    assert data.tell() == 0
+
+
+def test_detect_format_filelike_position_on_error():
+    class FailingIO(BytesIO):
+        def read(self, *args):
+            super().read(*args)
+            raise OSError("read failed")
+
+    data = FailingIO((PATH / "base.xlsx").read_bytes())
+    data.seek(10)
+    with pytest.raises(OSError):
+        detect_format(data)
+    assert data.tell() == 10
+
+
+@pytest.mark.parametrize(
+    ("bof", "biff_version"),
+    [
+        (b"\x09\x00\x10\x00\x00\x06", 2),
+        (b"\x09\x02\x10\x00\x00\x06", 3),
+        (b"\x09\x04\x10\x00\x00\x06", 4),
+        (b"\x09\x08\x10\x00\x00\x05", 5),
+        (b"\x09\x08\x10\x00\x00\x07", None),
+    ],
+)
+def test_detect_format_biff_version(bof, biff_version):
+    # the globals substream starts with the first BOF record of the file
+    data = (PATH / "base.xls").read_bytes().replace(
+        b"\x09\x08\x10\x00\x00\x06", bof, 1
+    )
+
+    info = detect_format(data)
+    assert info.format == WorkbookFormatEnum.Xls
+    assert info.biff_version == biff_version
+
+
+@pytest.mark.parametrize(
+    ("file", "format", "biff_version", "date_system"),
+    [
+        ("base.xls", WorkbookFormatEnum.Xls, 8, 1900),
+        ("base.xlsx", WorkbookFormatEnum.Xlsx, None, 1900),
+        ("base.xlsb", WorkbookFormatEnum.Xlsb, None, 1900),
+        ("base.ods", WorkbookFormatEnum.Ods, None, None),
+    ],
+)
+def test_workbook_format(file, format, biff_version, date_system):
+    reader = CalamineWorkbook.from_object(PATH / file)
+
+    assert reader.format == format
+    assert reader.macros_enabled is False
+    assert reader.biff_version == biff_version
+    assert reader.date_system == date_system
+
+
+def test_workbook_format_filelike():
+    with open(PATH / "base.xls", "rb") as f:
+        reader = CalamineWorkbook.from_filelike(f)
+        assert reader.format == WorkbookFormatEnum.Xls
+        assert reader.biff_version == 8
+        assert reader.date_system == 1900
+
+    reader = CalamineWorkbook.from_object((PATH / "base.xlsb").read_bytes())
+    assert reader.format == WorkbookFormatEnum.Xlsb
+    assert reader.date_system == 1900
+
+
+def test_workbook_macros_enabled(tmp_path):
+    assert CalamineWorkbook.from_object(PATH / "any_sheets.xls").macros_enabled
+
+    path = tmp_path / "base.xlsm"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        path,
+        "[Content_Types].xml",
+        XLSX_CONTENT_TYPE,
+        XLSM_CONTENT_TYPE,
+    )
+    reader = CalamineWorkbook.from_object(path)
+    assert reader.format == WorkbookFormatEnum.Xlsx
+    assert reader.macros_enabled is True
+
+
+def test_workbook_date_system_1904(tmp_path):
+    path = tmp_path / "base.xlsx"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        path,
+        "xl/workbook.xml",
+        b"<workbookPr ",
+        b'<workbookPr date1904="1" ',
+    )
+
+    assert CalamineWorkbook.from_object(path).date_system == 1904
@@ -0,0 +1,94 @@
+from pathlib import Path
+
+import pytest
+from python_calamine import CalamineWorkbook, WorkbookClosed
+
+from tests.utils import replace_in_zip
+
+PATH = Path(__file__).parent / "data"
+
+
+def make_xlsx(tmp_path):
+    path = tmp_path / "formulas.xlsx"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        path,
+        "xl/worksheets/sheet1.xml",
+        b'<c r="C2"><v>',
+        b'<c r="C2"><f>B2+0.1</f><v>',
+    )
+    return path
+
+
+def make_ods(tmp_path):
+    path = tmp_path / "formulas.ods"
+    replace_in_zip(
+        PATH / "base.ods",
+        path,
+        "content.xml",
+        b'<table:table-cell office:value-type="float" office:value="1.1"',
+        b'<table:table-cell table:formula="of:=[.B2]+0.1"'
+        b' office:value-type="float" office:value="1.1"',
+    )
+    return path
+
+
+def make_xlsx_without_values(tmp_path):
+    path = tmp_path / "formulas_without_values.xlsx"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        path,
+        "xl/worksheets/sheet1.xml",
+        b"</sheetData>",
+        b'<row r="4"><c r="A4"><f>1+1</f></c><c r="L4"><f>B2*2</f></c></row>'
+        b"</sheetData>",
+    )
+    return path
+
+
+@pytest.mark.parametrize(
+    ("make_path", "formula"),
+    [(make_xlsx, "B2+0.1"), (make_ods, "of:=[.B2]+0.1")],
+)
+def test_get_sheet_formulas(make_path, formula, tmp_path):
+    reader = CalamineWorkbook.from_object(make_path(tmp_path))
+
+    values = reader.get_sheet_by_name("Sheet1").to_python()
+    formulas = reader.get_sheet_formulas("Sheet1")
+    assert formulas == [["", "", formula, "", "", "", "", "", "", ""]]
+    assert values[0][2] == 1.1
+
+    values = reader.get_sheet_by_name("Sheet1").to_python(skip_empty_area=False)
+    formulas = reader.get_sheet_formulas("Sheet1", skip_empty_area=False)
+    assert [len(row) for row in formulas] == [len(row) for row in values]
+    assert formulas[1][2] == formula
+    assert values[1][2] == 1.1
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb", "base.ods"])
+def test_get_sheet_formulas_without_formulas(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+
+    assert reader.get_sheet_formulas("Sheet1") == [[""] * 10]
+    assert reader.get_sheet_formulas("Sheet2") == []
+
+
+@pytest.mark.parametrize("skip_empty_area", [True, False])
+def test_get_sheet_formulas_without_values(tmp_path, skip_empty_area):
+    reader = CalamineWorkbook.from_object(make_xlsx_without_values(tmp_path))
+
+    # formulas without a cached value outside of the values are left out
+    values = reader.get_sheet_by_name("Sheet1").to_python(
+        skip_empty_area=skip_empty_area
+    )
+    formulas = reader.get_sheet_formulas("Sheet1", skip_empty_area=skip_empty_area)
+    assert [len(row) for row in formulas] == [len(row) for row in values]
+    assert not any(any(row) for row in formulas)
+
+
+def test_get_sheet_formulas_closed():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    reader.close()
+
+    with pytest.raises(WorkbookClosed):
+        reader.get_sheet_formulas("Sheet1")
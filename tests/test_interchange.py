@@ -0,0 +1,168 @@
+import ctypes
+import struct
+from datetime import date, datetime, timedelta
+from pathlib import Path
+
+import pytest
+from python_calamine import CalamineWorkbook
+
+try:
+    import pandas as pd
+except ImportError:
+    pd = None
+
+try:
+    import polars as pl
+except ImportError:
+    pl = None
+
+PATH = Path(__file__).parent / "data"
+MICROSECOND = timedelta(microseconds=1)
+
+
+def read_column(column):
+    """Reads the values of a column from its buffers."""
+    buffers = column.get_buffers()
+    data, data_dtype = buffers["data"]
+    validity, _ = buffers["validity"]
+    size = column.size()
+    raw = ctypes.string_at(data.ptr, data.bufsize)
+    if buffers["offsets"] is not None:
+        offsets, _ = buffers["offsets"]
+        offsets = struct.unpack(
+            f"={size + 1}q", ctypes.string_at(offsets.ptr, offsets.bufsize)
+        )
+        values = [raw[offsets[i] : offsets[i + 1]].decode() for i in range(size)]
+    else:
+        code = {"l": "q", "i": "i", "g": "d", "b": "?"}[data_dtype[2]]
+        values = list(struct.unpack(f"={size}{code}", raw))
+    mask = ctypes.string_at(validity.ptr, validity.bufsize)
+    return [value if valid else None for value, valid in zip(values, mask)]
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb", "base.ods"])
+def test_sheet_dataframe(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+    df = reader.get_sheet_by_name("Sheet1", header_row=0).__dataframe__()
+
+    assert df.num_columns() == 10
+    assert df.num_rows() == 1
+    assert df.column_names() == [f"Unnamed: {i}" for i in range(10)]
+    assert [column.dtype for column in df.get_columns()][:7] == [
+        (21, 8, "U", "="),
+        (2, 64, "g", "="),
+        (2, 64, "g", "="),
+        (20, 8, "b", "="),
+        (20, 8, "b", "="),
+        (22, 32, "tdD", "="),
+        (22, 64, "tsu:", "="),
+    ]
+    # dates and datetimes are days and microseconds since the epoch
+    days = (date(2010, 10, 10) - date(1970, 1, 1)).days
+    micros = (datetime(2010, 10, 10, 10, 10, 10) - datetime(1970, 1, 1)) // MICROSECOND
+    assert [read_column(column) for column in df.get_columns()][:8] == [
+        ["String"],
+        [1.0],
+        [1.1],
+        [True],
+        [False],
+        [days],
+        [micros],
+        ["10:10:10"],
+    ]
+
+
+def test_sheet_dataframe_options():
+    reader = CalamineWorkbook.from_object(PATH / "table-multiple.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet3")
+
+    df = sheet.__dataframe__()
+    assert df.column_names() == ["Item", "Units"]
+    column = df.get_column_by_name("Item")
+    assert column.null_count == 1
+    assert column.describe_null == (4, 0)
+    assert read_column(column)[3:6] == ["4.0", None, "Item"]
+
+    # the chunks have the type of the whole column
+    chunks = df.select_columns_by_name(["Units"]).get_chunks(3)
+    assert [chunk.num_rows() for chunk in chunks] == [4, 4, 2]
+    assert read_column(chunks[0].get_column(0)) == ["1000.0", "500.0", "200.0", "50.0"]
+
+    df = sheet.__dataframe__(header_row=None)
+    assert df.column_names() == ["0", "1"]
+    assert df.num_rows() == 11
+
+    with pytest.raises(KeyError):
+        df.get_column_by_name("Item")
+    with pytest.raises(TypeError):
+        df.get_column(0).describe_categorical
+
+    df = reader.get_sheet_by_name("Sheet2").__dataframe__(header_row=10)
+    assert df.column_names() == ["Unnamed: 0", "Unnamed: 1"]
+    assert df.num_rows() == 0
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.ods"])
+def test_buffers_alignment(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+    df = reader.get_sheet_by_name("Sheet1", header_row=0).__dataframe__()
+
+    # data buffers are aligned for their values, offsets for int64
+    for column in df.get_columns():
+        buffers = column.get_buffers()
+        data, (_, bit_width, _, _) = buffers["data"]
+        assert data.ptr % (bit_width // 8) == 0
+        if buffers["offsets"] is None:
+            assert data.bufsize == column.size() * bit_width // 8
+        else:
+            assert buffers["offsets"][0].ptr % 8 == 0
+
+
+def test_table_dataframe():
+    reader = CalamineWorkbook.from_object(
+        PATH / "table-multiple.xlsx", load_tables=True
+    )
+    df = reader.get_table_by_name("Inventory").__dataframe__()
+
+    assert df.column_names() == ["Item", "Type", "Quantity"]
+    assert df.__dataframe__() is df
+    columns = {
+        name: read_column(df.get_column_by_name(name)) for name in df.column_names()
+    }
+    assert columns == {
+        "Item": [1.0, 2.0, 3.0, 4.0],
+        "Type": ["Apple", "Banana", "Orange", "Pear"],
+        "Quantity": [50.0, 200.0, 60.0, 100.0],
+    }
+
+
+@pytest.mark.skipif(not pd, reason="pandas is required")
+def test_dataframe_to_pandas():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    result = pd.api.interchange.from_dataframe(
+        reader.get_sheet_by_name("Sheet1", header_row=0)
+    )
+
+    assert result.iloc[0].tolist()[:7] == [
+        "String",
+        1.0,
+        1.1,
+        True,
+        False,
+        pd.Timestamp("2010-10-10"),
+        pd.Timestamp("2010-10-10 10:10:10"),
+    ]
+
+
+@pytest.mark.skipif(not pl, reason="polars is required")
+def test_dataframe_to_polars():
+    reader = CalamineWorkbook.from_object(
+        PATH / "table-multiple.xlsx", load_tables=True
+    )
+    result = pl.from_dataframe(reader.get_table_by_name("Inventory"))
+
+    assert result.to_dict(as_series=False) == {
+        "Item": [1.0, 2.0, 3.0, 4.0],
+        "Type": ["Apple", "Banana", "Orange", "Pear"],
+        "Quantity": [50.0, 200.0, 60.0, 100.0],
+    }
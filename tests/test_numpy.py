@@ -0,0 +1,104 @@
+from datetime import date, datetime, time, timedelta
+from pathlib import Path
+
+import pytest
+from python_calamine import CalamineWorkbook
+
+try:
+    import numpy as np
+except ImportError:
+    np = None
+
+PATH = Path(__file__).parent / "data"
+
+
+@pytest.mark.skipif(not np, reason="numpy is required")
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb"])
+def test_sheet_to_columns(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+    columns = reader.get_sheet_by_name("Sheet1", header_row=0).to_columns()
+
+    assert list(columns) == [f"Unnamed: {i}" for i in range(10)]
+    assert [str(column.dtype) for column in columns.values()] == [
+        "object",
+        "float64",
+        "float64",
+        "bool",
+        "bool",
+        "datetime64[D]",
+        "datetime64[us]",
+        "object",
+        "timedelta64[us]",
+        "timedelta64[us]",
+    ]
+    assert [column.tolist() for column in columns.values()] == [
+        ["String"],
+        [1.0],
+        [1.1],
+        [True],
+        [False],
+        [date(2010, 10, 10)],
+        [datetime(2010, 10, 10, 10, 10, 10)],
+        [time(10, 10, 10)],
+        [timedelta(hours=10, minutes=10, seconds=10, microseconds=100000)],
+        [timedelta(hours=255, minutes=10, seconds=10)],
+    ]
+
+
+@pytest.mark.skipif(not np, reason="numpy is required")
+def test_sheet_to_columns_empty_cells():
+    reader = CalamineWorkbook.from_object(PATH / "table-multiple.xlsx")
+    columns = reader.get_sheet_by_name("Sheet3").to_columns()
+
+    # the second table of the sheet makes the columns mixed
+    assert columns["Item"].dtype == np.dtype("object")
+    assert columns["Item"].tolist() == [
+        1.0,
+        2.0,
+        3.0,
+        4.0,
+        None,
+        "Item",
+        1.0,
+        2.0,
+        3.0,
+        4.0,
+    ]
+
+
+@pytest.mark.skipif(not np, reason="numpy is required")
+def test_sheet_to_numpy():
+    reader = CalamineWorkbook.from_object(PATH / "table-multiple.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet2")
+
+    array = sheet.to_numpy()
+    assert array.shape == (5, 2)
+    assert array.dtype == np.dtype("object")
+    assert array[1:].tolist() == [[1.0, 0.85], [2.0, 0.75], [3.0, 0.55], [4.0, 0.45]]
+
+    array = reader.get_sheet_by_name("Sheet3").to_numpy()
+    assert array.shape == (11, 2)
+    assert array[5].tolist() == [None, None]
+
+
+@pytest.mark.skipif(not np, reason="numpy is required")
+def test_sheet_to_numpy_dtype():
+    reader = CalamineWorkbook.from_object(PATH / "table-multiple.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet2", skiprows=1)
+
+    array = sheet.to_numpy()
+    assert array.dtype == np.dtype("float64")
+    assert array.tolist() == [[1.0, 0.85], [2.0, 0.75], [3.0, 0.55], [4.0, 0.45]]
+
+    array = sheet.to_numpy(dtype="int64")
+    assert array.dtype == np.dtype("int64")
+    assert array[:, 0].tolist() == [1, 2, 3, 4]
+
+
+@pytest.mark.skipif(not np, reason="numpy is required")
+def test_sheet_to_numpy_empty():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet2")
+
+    assert sheet.to_numpy().shape == (0, 0)
+    assert sheet.to_columns() == {}
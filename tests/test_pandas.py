@@ -1,6 +1,8 @@
+from datetime import date, datetime, time, timedelta
 from pathlib import Path
 
 import pytest
+from python_calamine import CalamineWorkbook
 
 try:
     import pandas as pd
@@ -110,3 +112,71 @@ def test_xlsb_openpyxl_pandas():
     )
 
     tm.assert_frame_equal(result_calamine, result_default)
+
+
+@pytest.mark.skipif(not pd, reason="pandas is required")
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb"])
+def test_sheet_to_pandas(path):
+    reader = CalamineWorkbook.from_object(PATH / path)
+    result = reader.get_sheet_by_name("Sheet1", header_row=0).to_pandas()
+
+    assert result.columns.tolist() == [f"Unnamed: {i}" for i in range(10)]
+    assert result["Unnamed: 1"].dtype == "float64"
+    assert result["Unnamed: 3"].dtype == "bool"
+    assert result["Unnamed: 5"].dtype.kind == "M"
+    assert result["Unnamed: 6"].dtype.kind == "M"
+    assert result["Unnamed: 8"].dtype.kind == "m"
+    assert result.iloc[0].tolist() == [
+        "String",
+        1.0,
+        1.1,
+        True,
+        False,
+        pd.Timestamp("2010-10-10"),
+        pd.Timestamp("2010-10-10 10:10:10"),
+        time(10, 10, 10),
+        pd.Timedelta(hours=10, minutes=10, seconds=10, microseconds=100000),
+        pd.Timedelta(hours=255, minutes=10, seconds=10),
+    ]
+
+
+@pytest.mark.skipif(not pd, reason="pandas is required")
+def test_sheet_to_pandas_options():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet1")
+
+    result = sheet.to_pandas(header=None, parse_dates=False)
+    assert result.columns.tolist() == list(range(10))
+    assert result[5].dtype == "object"
+    assert result[5].tolist() == [date(2010, 10, 10)]
+    assert result[6].tolist() == [datetime(2010, 10, 10, 10, 10, 10)]
+    assert result[8].tolist() == [
+        timedelta(hours=10, minutes=10, seconds=10, microseconds=100000)
+    ]
+
+    reader = CalamineWorkbook.from_object(PATH / "table-multiple.xlsx")
+    sheet = reader.get_sheet_by_name("Sheet2")
+
+    result = sheet.to_pandas(index_col=0, dtype={"Item": "int64"})
+    assert result.index.name == "Item"
+    assert result.index.tolist() == [1, 2, 3, 4]
+    assert result["Price"].tolist() == [0.85, 0.75, 0.55, 0.45]
+
+    result = sheet.to_pandas(index_col="Price", dtype="float32")
+    assert result.index.name == "Price"
+    assert result["Item"].dtype == "float32"
+
+
+@pytest.mark.skipif(not pd, reason="pandas is required")
+def test_sheet_to_pandas_empty_cells():
+    reader = CalamineWorkbook.from_object(PATH / "table-multiple.xlsx")
+    result = reader.get_sheet_by_name("Sheet3").to_pandas()
+
+    # the second table of the sheet makes the columns mixed
+    assert result.columns.tolist() == ["Item", "Units"]
+    assert result["Item"].dtype == "object"
+    assert result["Item"][4] is None
+    assert result["Item"][5] == "Item"
+
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    assert reader.get_sheet_by_name("Sheet2").to_pandas().empty
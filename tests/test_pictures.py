@@ -0,0 +1,97 @@
+from pathlib import Path
+
+import pytest
+from python_calamine import CalamineWorkbook, Picture, WorkbookClosed
+
+from tests.utils import add_to_zip, replace_in_zip
+
+PATH = Path(__file__).parent / "data"
+
+PNG = b"\x89PNG\r\n\x1a\n" + b"\x00" * 16
+
+DRAWING = b"""<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<xdr:wsDr xmlns:xdr="http://schemas.openxmlformats.org/drawingml/2006/spreadsheetDrawing" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+<xdr:oneCellAnchor>
+<xdr:from><xdr:col>2</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>4</xdr:row><xdr:rowOff>0</xdr:rowOff></xdr:from>
+<xdr:ext cx="952500" cy="952500"/>
+<xdr:pic>
+<xdr:nvPicPr><xdr:cNvPr id="2" name="Picture 1"/><xdr:cNvPicPr/></xdr:nvPicPr>
+<xdr:blipFill><a:blip r:embed="rId1"/><a:stretch><a:fillRect/></a:stretch></xdr:blipFill>
+<xdr:spPr><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></xdr:spPr>
+</xdr:pic>
+<xdr:clientData/>
+</xdr:oneCellAnchor>
+</xdr:wsDr>"""
+
+DRAWING_RELS = b"""<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/image1.png"/>
+</Relationships>"""
+
+SHEET_RELS = b"""<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/drawing" Target="../drawings/drawing1.xml"/>
+</Relationships>"""
+
+
+def make_xlsx(path, anchored=True):
+    replace_in_zip(
+        PATH / "base.xlsx",
+        path,
+        "xl/worksheets/sheet1.xml",
+        b"</worksheet>",
+        b'<drawing r:id="rId1"/></worksheet>' if anchored else b"</worksheet>",
+    )
+    files = {"xl/media/image1.png": PNG}
+    if anchored:
+        files.update(
+            {
+                "xl/drawings/drawing1.xml": DRAWING,
+                "xl/drawings/_rels/drawing1.xml.rels": DRAWING_RELS,
+                "xl/worksheets/_rels/sheet1.xml.rels": SHEET_RELS,
+            }
+        )
+    add_to_zip(path, files)
+    return path
+
+
+def test_pictures_xlsx(tmp_path):
+    reader = CalamineWorkbook.from_object(make_xlsx(tmp_path / "pictures.xlsx"))
+
+    pictures = reader.pictures
+    assert len(pictures) == 1
+    picture = pictures[0]
+    assert isinstance(picture, Picture)
+    assert picture.extension == "png"
+    assert picture.data == PNG
+    assert picture.sheet == "Sheet1"
+    assert picture.anchor == (4, 2)
+    assert picture.name == "Picture 1"
+    assert repr(picture) == (
+        "Picture(extension='png', size=24, sheet='Sheet1', anchor=(4, 2))"
+    )
+
+
+def test_pictures_xlsx_not_anchored(tmp_path):
+    path = make_xlsx(tmp_path / "pictures.xlsx", anchored=False)
+    reader = CalamineWorkbook.from_object(path)
+
+    pictures = reader.pictures
+    assert len(pictures) == 1
+    assert pictures[0].data == PNG
+    assert pictures[0].sheet is None
+    assert pictures[0].anchor is None
+    assert pictures[0].name is None
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb", "base.ods"])
+def test_pictures_empty(path):
+    assert CalamineWorkbook.from_object(PATH / path).pictures == []
+
+
+def test_pictures_closed():
+    reader = CalamineWorkbook.from_object(PATH / "base.xlsx")
+    reader.close()
+
+    with pytest.raises(WorkbookClosed):
+        reader.pictures
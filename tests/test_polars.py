@@ -0,0 +1,104 @@
+from pathlib import Path
+
+import pytest
+from python_calamine import scan_sheet
+
+from tests.utils import replace_in_zip
+
+try:
+    import polars as pl
+except ImportError:
+    pl = None
+
+PATH = Path(__file__).parent / "data"
+
+
+@pytest.mark.skipif(not pl, reason="polars is required")
+def test_scan_sheet():
+    lf = scan_sheet(PATH / "table-multiple.xlsx", "Sheet2")
+
+    assert lf.collect_schema() == pl.Schema({"Item": pl.Float64, "Price": pl.Float64})
+    assert lf.collect().to_dict(as_series=False) == {
+        "Item": [1.0, 2.0, 3.0, 4.0],
+        "Price": [0.85, 0.75, 0.55, 0.45],
+    }
+
+
+@pytest.mark.skipif(not pl, reason="polars is required")
+def test_scan_sheet_pushdown():
+    lf = scan_sheet(PATH / "table-multiple.xlsx", 1)
+
+    assert lf.select("Price").head(2).collect().to_dict(as_series=False) == {
+        "Price": [0.85, 0.75]
+    }
+    assert lf.filter(pl.col("Item") > 2).collect().to_dict(as_series=False) == {
+        "Item": [3.0, 4.0],
+        "Price": [0.55, 0.45],
+    }
+
+
+@pytest.mark.skipif(not pl, reason="polars is required")
+def test_scan_sheet_types():
+    lf = scan_sheet(PATH / "base.xlsx", "Sheet1")
+    df = lf.collect()
+
+    # the only row of the sheet is the header
+    assert df.height == 0
+    assert lf.collect_schema()["String"] == pl.Null
+
+    df = scan_sheet(PATH / "table-multiple.xlsx", "Sheet3").collect()
+    assert df.schema == pl.Schema({"Item": pl.String, "Units": pl.String})
+    assert df["Item"].to_list()[3:6] == ["4.0", None, "Item"]
+
+    with pytest.raises(pl.exceptions.PolarsError):
+        scan_sheet(PATH / "table-multiple.xlsx", "Sheet2").select("Units").collect()
+
+
+@pytest.mark.skipif(not pl, reason="polars is required")
+def test_scan_sheet_infer_schema_length():
+    path = PATH / "table-multiple.xlsx"
+
+    # the column turns into strings after the first table, they are nulls
+    lf = scan_sheet(path, "Sheet3", infer_schema_length=1)
+    assert lf.collect_schema()["Item"] == pl.Float64
+    assert lf.head(2).collect()["Item"].to_list() == [1.0, 2.0]
+    assert lf.collect()["Item"].to_list()[3:7] == [4.0, None, None, 1.0]
+
+    lf = scan_sheet(path, "Sheet3", infer_schema_length=None)
+    assert lf.collect_schema()["Item"] == pl.String
+    assert lf.head(2).collect()["Item"].to_list() == ["1.0", "2.0"]
+
+
+@pytest.mark.skipif(not pl, reason="polars is required")
+def test_scan_sheet_columns_after_schema(tmp_path):
+    path = tmp_path / "base.xlsx"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        tmp_path / "dimension.xlsx",
+        "xl/worksheets/sheet2.xml",
+        b'<dimension ref="A1"/>',
+        b'<dimension ref="A1:C3"/>',
+    )
+    replace_in_zip(
+        tmp_path / "dimension.xlsx",
+        path,
+        "xl/worksheets/sheet2.xml",
+        b"<sheetData/>",
+        b'<sheetData><row r="1"><c r="A1" t="inlineStr"><is><t>a</t></is></c></row>'
+        b'<row r="3"><c r="C3"><v>1</v></c></row></sheetData>',
+    )
+
+    # the column of the last row is in the schema read from the first rows
+    lf = scan_sheet(path, "Sheet2", infer_schema_length=1)
+    assert list(lf.collect_schema()) == ["a", "Unnamed: 1", "Unnamed: 2"]
+    df = lf.collect()
+    assert df.columns == ["a", "Unnamed: 1", "Unnamed: 2"]
+    assert df.height == 2
+
+
+def test_scan_sheet_without_polars():
+    if pl is not None:
+        pytest.skip("polars is installed")
+
+    with pytest.raises(ImportError):
+        scan_sheet(PATH / "table-multiple.xlsx", "Sheet2")
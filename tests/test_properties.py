@@ -0,0 +1,186 @@
+import shutil
+import struct
+from datetime import datetime
+from pathlib import Path
+
+import pytest
+from python_calamine import CalamineWorkbook, DocumentProperties
+
+from tests.utils import add_to_zip, replace_in_zip
+
+PATH = Path(__file__).parent / "data"
+
+
+@pytest.mark.parametrize(
+    ("path", "modified", "app_version"),
+    [
+        ("any_sheets.xls", datetime(2023, 7, 28, 16, 31, 33), "16.0000"),
+        ("any_sheets.xlsx", datetime(2023, 7, 28, 16, 31, 17), "16.0300"),
+        ("any_sheets.xlsb", datetime(2023, 7, 28, 16, 31, 46), "16.0300"),
+    ],
+)
+def test_document_properties_excel(path, modified, app_version):
+    properties = CalamineWorkbook.from_object(PATH / path).document_properties
+
+    assert isinstance(properties, DocumentProperties)
+    assert properties.title is None
+    assert properties.subject is None
+    assert properties.keywords is None
+    assert properties.creator == "Алена"
+    assert properties.last_modified_by == "Алена"
+    assert properties.created == datetime(2023, 7, 28, 16, 28, 6)
+    assert properties.modified == modified
+    assert properties.company is None
+    assert properties.application == "Microsoft Excel"
+    assert properties.app_version == app_version
+
+
+def test_document_properties_ods():
+    properties = CalamineWorkbook.from_object(PATH / "base.ods").document_properties
+
+    assert properties.creator is None
+    assert properties.created is None
+    assert properties.modified == datetime(2025, 4, 14, 11, 14, 40, 502529)
+    assert properties.application == "LibreOffice"
+    assert properties.app_version == "24.2.7.2"
+
+
+def test_document_properties_xlsx_core(tmp_path):
+    path = tmp_path / "properties.xlsx"
+    replace_in_zip(
+        PATH / "base.xlsx",
+        path,
+        "docProps/core.xml",
+        b"<dc:title></dc:title><dc:subject></dc:subject><dc:creator></dc:creator>",
+        b"<dc:title>Receipts &amp; invoices</dc:title><dc:subject>Q3</dc:subject>"
+        b"<dc:creator>Jane</dc:creator><cp:keywords>receipts, 2025</cp:keywords>"
+        b"<cp:lastModifiedBy>John</cp:lastModifiedBy>",
+    )
+    properties = CalamineWorkbook.from_object(path).document_properties
+
+    assert properties.title == "Receipts & invoices"
+    assert properties.subject == "Q3"
+    assert properties.creator == "Jane"
+    assert properties.keywords == "receipts, 2025"
+    assert properties.last_modified_by == "John"
+    assert repr(properties) == (
+        "DocumentProperties(title='Receipts & invoices', creator='Jane', "
+        "modified='2025-04-13 17:29:27')"
+    )
+
+
+def test_document_properties_ods_meta(tmp_path):
+    path = tmp_path / "properties.ods"
+    replace_in_zip(
+        PATH / "any_sheets.ods",
+        path,
+        "meta.xml",
+        b"<dc:creator>",
+        b"<dc:title>Receipts</dc:title><meta:keyword>receipts</meta:keyword>"
+        b"<meta:keyword>2025</meta:keyword><dc:creator>",
+    )
+    properties = CalamineWorkbook.from_object(path).document_properties
+
+    assert properties.title == "Receipts"
+    assert properties.keywords == "receipts, 2025"
+    assert properties.application == "MicrosoftOffice"
+    assert properties.app_version == "16.0"
+
+
+def test_document_properties_password():
+    reader = CalamineWorkbook.from_object(PATH / "password.xlsb", password="1234")
+
+    assert reader.document_properties.creator == "Алена"
+
+
+CUSTOM_XML = b"""<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/custom-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes">
+<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="2" name="TemplateVersion"><vt:lpwstr>3.1</vt:lpwstr></property>
+<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="3" name="Department"><vt:lpwstr>R&amp;D</vt:lpwstr></property>
+<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="4" name="Revision"><vt:i4>12</vt:i4></property>
+<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="5" name="Rate"><vt:r8>0.25</vt:r8></property>
+<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="6" name="Approved"><vt:bool>true</vt:bool></property>
+<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="7" name="Issued"><vt:filetime>2025-03-01T09:30:00Z</vt:filetime></property>
+</Properties>"""
+
+CUSTOM_PROPERTIES = {
+    "TemplateVersion": "3.1",
+    "Department": "R&D",
+    "Revision": 12,
+    "Rate": 0.25,
+    "Approved": True,
+    "Issued": datetime(2025, 3, 1, 9, 30),
+}
+
+
+def test_custom_properties_xlsx(tmp_path):
+    path = tmp_path / "custom.xlsx"
+    shutil.copy(PATH / "base.xlsx", path)
+    add_to_zip(path, {"docProps/custom.xml": CUSTOM_XML})
+    properties = CalamineWorkbook.from_object(path).custom_properties
+
+    assert properties == CUSTOM_PROPERTIES
+    assert list(properties) == list(CUSTOM_PROPERTIES)
+    assert isinstance(properties["Revision"], int)
+
+
+def test_custom_properties_ods(tmp_path):
+    path = tmp_path / "custom.ods"
+    replace_in_zip(
+        PATH / "base.ods",
+        path,
+        "meta.xml",
+        b"</office:meta>",
+        b'<meta:user-defined meta:name="TemplateVersion">3.1</meta:user-defined>'
+        b'<meta:user-defined meta:name="Department" meta:value-type="string">'
+        b"R&amp;D</meta:user-defined>"
+        b'<meta:user-defined meta:name="Revision" meta:value-type="float">'
+        b"12</meta:user-defined>"
+        b'<meta:user-defined meta:name="Approved" meta:value-type="boolean">'
+        b"true</meta:user-defined>"
+        b'<meta:user-defined meta:name="Issued" meta:value-type="date">'
+        b"2025-03-01T09:30:00</meta:user-defined>"
+        b'<meta:user-defined meta:name="Empty"/>'
+        b"</office:meta>",
+    )
+    properties = CalamineWorkbook.from_object(path).custom_properties
+
+    assert properties == {
+        "TemplateVersion": "3.1",
+        "Department": "R&D",
+        "Revision": 12.0,
+        "Approved": True,
+        "Issued": datetime(2025, 3, 1, 9, 30),
+        "Empty": "",
+    }
+
+
+def test_custom_properties_xls():
+    properties = CalamineWorkbook.from_object(PATH / "base.xls").custom_properties
+
+    assert properties == {"AppVersion": "15.0000"}
+
+
+@pytest.mark.parametrize(
+    ("days", "expected"),
+    [
+        (45658.5, datetime(2025, 1, 1, 12)),
+        (float("inf"), None),
+        (float("nan"), None),
+        (1e300, None),
+    ],
+)
+def test_custom_properties_xls_date(days, expected):
+    # AppVersion turned into a VT_DATE, the size of the property is kept
+    data = (PATH / "base.xls").read_bytes()
+    old = b"\x1e\x00\x00\x00\x08\x00\x00\x0015.0000"
+    new = b"\x07\x00\x00\x00" + struct.pack("<d", days) + b"000"
+    assert data.count(old) == 1
+    properties = CalamineWorkbook.from_object(data.replace(old, new)).custom_properties
+
+    assert properties == ({} if expected is None else {"AppVersion": expected})
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xlsb", "base.ods", "any_sheets.xls"])
+def test_custom_properties_empty(path):
+    assert CalamineWorkbook.from_object(PATH / path).custom_properties == {}
@@ -57,6 +57,40 @@ def test_table_get_by_name():
     ]
 
 
+def test_table_records():
+    reader = CalamineWorkbook.from_object(
+        PATH / "table-multiple.xlsx", load_tables=True
+    )
+
+    table = reader.get_table_by_name("Pricing")
+    records = [
+        {"Item": 1.0, "Price": 0.85},
+        {"Item": 2.0, "Price": 0.75},
+        {"Item": 3.0, "Price": 0.55},
+        {"Item": 4.0, "Price": 0.45},
+    ]
+
+    assert table.to_records() == records
+    assert list(table.iter_dicts()) == records
+
+    # the same rows from the sheet, keyed by its first row
+    sheet = reader.get_sheet_by_name("Sheet2")
+    assert sheet.to_records() == records
+    assert list(sheet.iter_dicts()) == records
+
+
+def test_table_empty_value():
+    reader = CalamineWorkbook.from_object(
+        PATH / "table-multiple.xlsx", load_tables=True
+    )
+    table = reader.get_table_by_name("Pricing")
+
+    # the table has no empty cells
+    assert table.to_python(empty_value=None) == table.to_python()
+    assert table.to_records(empty_value=None) == table.to_records()
+    assert list(table.iter_dicts(empty_value=None)) == table.to_records()
+
+
 def test_table_get_by_name_not_found():
     reader = CalamineWorkbook.from_object(
         PATH / "table-multiple.xlsx", load_tables=True
@@ -0,0 +1,38 @@
+from pathlib import Path
+
+import pytest
+from python_calamine import (
+    CalamineWorkbook,
+    VbaModuleTypeEnum,
+    VbaProject,
+    WorkbookClosed,
+)
+
+PATH = Path(__file__).parent / "data"
+
+
+def test_vba_project_xls():
+    reader = CalamineWorkbook.from_object(PATH / "any_sheets.xls")
+
+    project = reader.vba_project
+    assert isinstance(project, VbaProject)
+    assert project.module_names == ["Диаграмма4", "Лист1", "Лист2", "Лист3"]
+    assert [m.typ for m in project.modules] == [VbaModuleTypeEnum.Document] * 4
+    assert [r.name for r in project.references] == ["stdole", "Office"]
+
+    module = project.get_module("Лист1")
+    assert module.source.startswith('Attribute VB_Name = "Лист1"\r\n')
+    assert project.get_module("Module1") is None
+
+
+@pytest.mark.parametrize("path", ["base.xlsx", "base.xls", "base.xlsb", "base.ods"])
+def test_vba_project_none(path):
+    assert CalamineWorkbook.from_object(PATH / path).vba_project is None
+
+
+def test_vba_project_closed():
+    reader = CalamineWorkbook.from_object(PATH / "any_sheets.xls")
+    reader.close()
+
+    with pytest.raises(WorkbookClosed):
+        reader.vba_project
@@ -0,0 +1,17 @@
+import zipfile
+
+
+def replace_in_zip(src, dst, filename, old, new):
+    with zipfile.ZipFile(src) as src_zip, zipfile.ZipFile(dst, "w") as dst_zip:
+        for item in src_zip.infolist():
+            content = src_zip.read(item.filename)
+            if item.filename == filename:
+                assert old in content
+                content = content.replace(old, new)
+            dst_zip.writestr(item, content)
+
+
+def add_to_zip(path, files):
+    with zipfile.ZipFile(path, "a") as zip_file:
+        for filename, content in files.items():
+            zip_file.writestr(filename, content)